@@ -0,0 +1,370 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::geo::{haversine_km, Located};
+use crate::workspace::{State, Workspace};
+use crate::{
+    CsrGraph, DijkstraError, Edge, Graph, GraphEdge, GraphVertex, Path, TieBreak, TotalF64, Vertex,
+    VertexIndex, Weight,
+};
+
+/// Estimates must never exceed the true remaining cost, and must not drop by
+/// more than a lane's weight when moving across that lane.
+pub trait Heuristic<V, W> {
+    fn estimate(&self, from: &V, to: &V) -> W;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoHeuristic;
+
+impl<V, W: Weight> Heuristic<V, W> for NoHeuristic {
+    fn estimate(&self, _from: &V, _to: &V) -> W {
+        W::zero()
+    }
+}
+
+impl<V, W, F: Fn(&V, &V) -> W> Heuristic<V, W> for F {
+    fn estimate(&self, from: &V, to: &V) -> W {
+        self(from, to)
+    }
+}
+
+pub trait FromEstimate {
+    fn from_estimate(value: f64) -> Self;
+}
+
+macro_rules! integer_estimate {
+    ($($t:ty),*) => {
+        $(
+            impl FromEstimate for $t {
+                fn from_estimate(value: f64) -> Self {
+                    value.max(0.0).floor() as $t
+                }
+            }
+        )*
+    };
+}
+
+integer_estimate!(i32, i64, u32, u64);
+
+impl FromEstimate for TotalF64 {
+    fn from_estimate(value: f64) -> Self {
+        TotalF64(value.max(0.0))
+    }
+}
+
+impl FromEstimate for Duration {
+    fn from_estimate(value: f64) -> Self {
+        Duration::from_nanos((value.max(0.0) * 1e9).floor() as u64)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct GreatCircle {
+    pub cost_per_km: f64,
+}
+
+impl GreatCircle {
+    pub fn new(cost_per_km: f64) -> Self {
+        GreatCircle { cost_per_km }
+    }
+}
+
+impl<V: Located, W: Weight + FromEstimate> Heuristic<V, W> for GreatCircle {
+    fn estimate(&self, from: &V, to: &V) -> W {
+        match (from.coordinates(), to.coordinates()) {
+            (Some(from), Some(to)) => W::from_estimate(haversine_km(from, to) * self.cost_per_km),
+            _ => W::zero(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AStar<V = Vertex, E = Edge, H = NoHeuristic>
+where
+    E: GraphEdge,
+{
+    graph: Arc<CsrGraph<V, E>>,
+    heuristic: H,
+    tie_break: TieBreak,
+    workspace: Workspace<E::Weight>,
+}
+
+impl<V, E, H> AStar<V, E, H>
+where
+    V: GraphVertex + Clone,
+    E: GraphEdge + Clone,
+    H: Heuristic<V, E::Weight>,
+{
+    pub fn new(graph: Graph<V, E>, heuristic: H) -> Self {
+        Self::from_csr(CsrGraph::from(graph), heuristic)
+    }
+
+    pub fn from_csr(graph: CsrGraph<V, E>, heuristic: H) -> Self {
+        Self::from_shared(Arc::new(graph), heuristic)
+    }
+
+    pub fn from_shared(graph: Arc<CsrGraph<V, E>>, heuristic: H) -> Self {
+        AStar {
+            graph,
+            heuristic,
+            tie_break: TieBreak::default(),
+            workspace: Workspace::new(),
+        }
+    }
+
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    pub fn graph(&self) -> &CsrGraph<V, E> {
+        &self.graph
+    }
+
+    pub fn run_to<S, T>(&mut self, source: &S, target: &T) -> Result<Path<V, E>, DijkstraError>
+    where
+        S: GraphVertex + ?Sized,
+        T: GraphVertex + ?Sized,
+    {
+        self.workspace.reset(self.graph.vertex_count());
+        if let Some(edge) = self.graph.negative_edge() {
+            return Err(DijkstraError::NegativeWeight(
+                self.graph.edge_id(edge).to_string(),
+            ));
+        }
+        let from = self
+            .graph
+            .vertex_index(source.id())
+            .ok_or_else(|| DijkstraError::UnknownSource(source.id().to_string()))?;
+        let to = self
+            .graph
+            .vertex_index(target.id())
+            .ok_or_else(|| DijkstraError::UnknownTarget(target.id().to_string()))?;
+
+        let zero = E::Weight::zero();
+        let estimate = self.estimate(zero, from, to);
+        self.workspace.start_with_priority(from, zero, estimate);
+        while let Some(State { cost, vertex }) = self.workspace.pop_unsettled() {
+            if self.workspace.is_settled(to) && cost > self.workspace.distance(to) {
+                break;
+            }
+            self.workspace.settle(vertex);
+            self.expand(vertex, to)?;
+        }
+
+        if !self.workspace.is_settled(to) {
+            return Err(DijkstraError::Unreachable(target.id().to_string()));
+        }
+        Ok(Path::from_predecessors(
+            &self.graph,
+            |vertex| self.workspace.predecessor(vertex),
+            to,
+            self.workspace.distance(to),
+        ))
+    }
+
+    fn expand(&mut self, node: VertexIndex, to: VertexIndex) -> Result<(), DijkstraError> {
+        for edge in self.graph.edge_range(node) {
+            let target = self.graph.edge_target(edge);
+            let cost = self
+                .workspace
+                .distance(node)
+                .checked_add(self.graph.edge_weight(edge));
+            if self.workspace.is_settled(target) {
+                if cost == Some(self.workspace.distance(target)) {
+                    self.tie_break
+                        .tie(&self.graph, &mut self.workspace, node, edge, target);
+                }
+                continue;
+            }
+            let cost = cost.ok_or(DijkstraError::Overflow)?;
+            let current = self.workspace.distance(target);
+            if cost < current {
+                let priority = self.estimate(cost, target, to);
+                self.workspace
+                    .improve_with_priority(target, cost, priority, node, edge);
+            } else if cost == current {
+                self.tie_break
+                    .tie(&self.graph, &mut self.workspace, node, edge, target);
+            }
+        }
+        Ok(())
+    }
+
+    fn estimate(&self, cost: E::Weight, from: VertexIndex, to: VertexIndex) -> E::Weight {
+        let remaining = self
+            .heuristic
+            .estimate(self.graph.vertex(from), self.graph.vertex(to));
+        cost.checked_add(remaining)
+            .unwrap_or_else(E::Weight::infinity)
+    }
+
+    pub fn settled_count(&self) -> usize {
+        self.workspace.settled().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{add_lane, vertices};
+    use crate::{Coordinates, Djikstra};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Depot {
+        code: &'static str,
+        latitude: f64,
+        longitude: f64,
+    }
+
+    impl GraphVertex for Depot {
+        fn id(&self) -> &str {
+            self.code
+        }
+    }
+
+    impl Located for Depot {
+        fn coordinates(&self) -> Option<Coordinates> {
+            Some(Coordinates::new(self.latitude, self.longitude))
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Road {
+        from: &'static str,
+        to: &'static str,
+        km: u32,
+    }
+
+    impl GraphEdge for Road {
+        type Weight = u32;
+
+        fn id(&self) -> &str {
+            self.from
+        }
+
+        fn source(&self) -> &str {
+            self.from
+        }
+
+        fn destination(&self) -> &str {
+            self.to
+        }
+
+        fn weight(&self) -> u32 {
+            self.km
+        }
+    }
+
+    fn network() -> Graph<Depot, Road> {
+        let depot = |code, latitude, longitude| Depot {
+            code,
+            latitude,
+            longitude,
+        };
+        let road = |from, to, km| Road { from, to, km };
+        Graph::new(
+            vec![
+                depot("HAM", 53.55, 9.99),
+                depot("BRE", 53.08, 8.80),
+                depot("HAN", 52.37, 9.73),
+                depot("KAS", 51.31, 9.48),
+                depot("FRA", 50.11, 8.68),
+                depot("WUE", 49.79, 9.95),
+                depot("NUE", 49.45, 11.08),
+                depot("MUC", 48.14, 11.58),
+                depot("BER", 52.52, 13.40),
+                depot("LEI", 51.34, 12.37),
+            ],
+            vec![
+                road("HAM", "BRE", 125),
+                road("HAM", "HAN", 155),
+                road("HAM", "BER", 290),
+                road("BRE", "HAN", 125),
+                road("HAN", "KAS", 165),
+                road("HAN", "BER", 285),
+                road("KAS", "FRA", 190),
+                road("KAS", "WUE", 210),
+                road("FRA", "WUE", 120),
+                road("WUE", "NUE", 110),
+                road("NUE", "MUC", 170),
+                road("BER", "LEI", 190),
+                road("LEI", "NUE", 280),
+            ],
+        )
+    }
+
+    #[test]
+    fn zero_heuristic_matches_dijkstra() {
+        let graph = network();
+        let mut djikstra = Djikstra::new(graph.clone());
+        let mut astar = AStar::new(graph.clone(), NoHeuristic);
+        for source in &graph.vertices {
+            for target in &graph.vertices {
+                assert_eq!(
+                    astar.run_to(source, target),
+                    djikstra.run_to(source, target)
+                );
+            }
+        }
+
+        let nodes = vertices(&["A", "B", "C", "D", "E"]);
+        let mut edges = vec![];
+        add_lane(&nodes, &mut edges, "AB".into(), 0, 1, 1);
+        add_lane(&nodes, &mut edges, "AC".into(), 0, 2, 1);
+        add_lane(&nodes, &mut edges, "CD".into(), 2, 3, 1);
+        add_lane(&nodes, &mut edges, "BD".into(), 1, 3, 1);
+        add_lane(&nodes, &mut edges, "AE".into(), 0, 4, 2);
+        add_lane(&nodes, &mut edges, "DE".into(), 3, 4, 0);
+        let tied = Graph::new(nodes.clone(), edges);
+        for &tie_break in &[
+            TieBreak::InsertionOrder,
+            TieBreak::FewestHops,
+            TieBreak::LowestVertexId,
+        ] {
+            let mut djikstra = Djikstra::new(tied.clone()).with_tie_break(tie_break);
+            let mut astar = AStar::new(tied.clone(), NoHeuristic).with_tie_break(tie_break);
+            for source in &nodes {
+                for target in &nodes {
+                    assert_eq!(
+                        astar.run_to(source, target),
+                        djikstra.run_to(source, target)
+                    );
+                }
+            }
+        }
+        let mut astar = AStar::new(tied, NoHeuristic);
+        assert_eq!(astar.run_to("A", "D").unwrap().edges, vec!["AC", "CD"]);
+        assert_eq!(astar.run_to("A", "E").unwrap().edges, vec!["AE"]);
+    }
+
+    #[test]
+    fn great_circle_explores_less() {
+        let graph = network();
+        let mut blind = AStar::new(graph.clone(), NoHeuristic);
+        let mut guided = AStar::new(graph, GreatCircle::new(1.0));
+
+        let expected = blind.run_to("HAM", "MUC").unwrap();
+        let path = guided.run_to("HAM", "MUC").unwrap();
+        assert_eq!(path, expected);
+        assert_eq!(path.cost, 810);
+        assert!(guided.settled_count() < blind.settled_count());
+
+        let mut custom = AStar::new(
+            network(),
+            |from: &Depot, to: &Depot| {
+                if from.code == to.code {
+                    0
+                } else {
+                    100
+                }
+            },
+        );
+        assert_eq!(custom.run_to("HAM", "MUC").unwrap().cost, 810);
+        assert_eq!(
+            custom.run_to("MUC", "HAM"),
+            Err(DijkstraError::Unreachable("HAM".into()))
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use std::sync::Arc;
+
+use crate::{
+    CsrGraph, DijkstraError, Edge, Graph, GraphEdge, GraphVertex, Path, Vertex, VertexIndex, Weight,
+};
+
+#[derive(Debug, Clone)]
+pub struct BellmanFord<V = Vertex, E = Edge>
+where
+    E: GraphEdge,
+{
+    graph: Arc<CsrGraph<V, E>>,
+    predecessors: Vec<Option<(VertexIndex, usize)>>,
+    distance: Vec<E::Weight>,
+}
+
+impl<V: GraphVertex + Clone, E: GraphEdge + Clone> BellmanFord<V, E> {
+    pub fn new(graph: Graph<V, E>) -> Self {
+        Self::from_csr(CsrGraph::from(graph))
+    }
+
+    pub fn from_csr(graph: CsrGraph<V, E>) -> Self {
+        Self::from_shared(Arc::new(graph))
+    }
+
+    pub fn from_shared(graph: Arc<CsrGraph<V, E>>) -> Self {
+        Self {
+            graph,
+            predecessors: Vec::new(),
+            distance: Vec::new(),
+        }
+    }
+
+    pub fn graph(&self) -> &CsrGraph<V, E> {
+        &self.graph
+    }
+
+    pub fn run<S: GraphVertex + ?Sized>(&mut self, source: &S) -> Result<(), DijkstraError> {
+        let vertex_count = self.graph.vertex_count();
+        self.distance = vec![E::Weight::infinity(); vertex_count];
+        self.predecessors = vec![None; vertex_count];
+
+        let source = self
+            .graph
+            .vertex_index(source.id())
+            .ok_or_else(|| DijkstraError::UnknownSource(source.id().to_string()))?;
+        self.distance[source.index()] = E::Weight::zero();
+
+        let outcome = self.relax_rounds();
+        if outcome.is_err() {
+            // The predecessors may now loop; forget them so no route is read back.
+            self.distance = vec![E::Weight::infinity(); vertex_count];
+            self.predecessors = vec![None; vertex_count];
+        }
+        outcome
+    }
+
+    pub fn run_to<S, T>(&mut self, source: &S, target: &T) -> Result<Path<V, E>, DijkstraError>
+    where
+        S: GraphVertex + ?Sized,
+        T: GraphVertex + ?Sized,
+    {
+        self.run(source)?;
+        self.get_route(target)
+    }
+
+    fn relax_rounds(&mut self) -> Result<(), DijkstraError> {
+        for _ in 1..self.graph.vertex_count() {
+            if self.relax_all()?.is_none() {
+                return Ok(());
+            }
+        }
+        match self.relax_all()? {
+            Some(vertex) => Err(self.cycle_error(vertex)),
+            None => Ok(()),
+        }
+    }
+
+    fn relax_all(&mut self) -> Result<Option<VertexIndex>, DijkstraError> {
+        let mut relaxed = None;
+        for node in 0..self.graph.vertex_count() {
+            let node = VertexIndex(node as u32);
+            let distance = self.distance[node.index()];
+            if distance == E::Weight::infinity() {
+                continue;
+            }
+            for edge in self.graph.edge_range(node) {
+                let target = self.graph.edge_target(edge);
+                let weight = self.graph.edge_weight(edge);
+                let cost = match distance.checked_add(weight) {
+                    Some(cost) => cost,
+                    // A negative cycle can run a distance past the weight's
+                    // range before the extra round would have caught it.
+                    None if weight.is_negative() => {
+                        self.predecessors[target.index()] = Some((node, edge));
+                        return Err(self.cycle_error(target));
+                    }
+                    None => return Err(DijkstraError::Overflow),
+                };
+                if cost < self.distance[target.index()] {
+                    self.distance[target.index()] = cost;
+                    self.predecessors[target.index()] = Some((node, edge));
+                    relaxed = Some(target);
+                }
+            }
+        }
+        Ok(relaxed)
+    }
+
+    fn cycle_error(&self, vertex: VertexIndex) -> DijkstraError {
+        self.cycle_through(vertex)
+            .map_or(DijkstraError::Overflow, DijkstraError::NegativeCycle)
+    }
+
+    fn cycle_through(&self, vertex: VertexIndex) -> Option<Vec<String>> {
+        let mut step = vertex;
+        for _ in 0..self.graph.vertex_count() {
+            step = self.predecessors[step.index()]?.0;
+        }
+
+        let start = step;
+        let mut lanes = vec![];
+        while let Some((previous, edge)) = self.predecessors[step.index()] {
+            lanes.push(self.graph.edge_id(edge).to_string());
+            step = previous;
+            if step == start {
+                break;
+            }
+        }
+        lanes.reverse();
+        Some(lanes)
+    }
+
+    pub fn get_path<T: GraphVertex + ?Sized>(
+        &self,
+        target: &T,
+    ) -> Result<Vec<String>, DijkstraError> {
+        self.get_route(target).map(|path| path.vertex_ids())
+    }
+
+    pub fn get_route<T: GraphVertex + ?Sized>(
+        &self,
+        target: &T,
+    ) -> Result<Path<V, E>, DijkstraError> {
+        let index = self
+            .graph
+            .vertex_index(target.id())
+            .ok_or_else(|| DijkstraError::UnknownTarget(target.id().to_string()))?;
+        match self.distance.get(index.index()) {
+            Some(&cost) if cost != E::Weight::infinity() => Ok(Path::from_predecessors(
+                &self.graph,
+                |vertex| self.predecessors[vertex.index()],
+                index,
+                cost,
+            )),
+            _ => Err(DijkstraError::Unreachable(target.id().to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vertices() -> Vec<Vertex> {
+        ["A", "B", "C", "D"]
+            .iter()
+            .map(|id| Vertex::new(id.to_string(), id.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn rebate_lanes() {
+        let v = vertices();
+        let edges = vec![
+            Edge::new("AB".into(), v[0].clone(), v[1].clone(), 10),
+            Edge::new("AC".into(), v[0].clone(), v[2].clone(), 4),
+            Edge::new("BC".into(), v[1].clone(), v[2].clone(), -8),
+            Edge::new("CD".into(), v[2].clone(), v[3].clone(), 5),
+        ];
+        let mut bellman_ford = BellmanFord::new(Graph::new(v.clone(), edges));
+        let path = bellman_ford.run_to(&v[0], &v[3]).unwrap();
+        assert_eq!(path.edges, vec!["AB", "BC", "CD"]);
+        assert_eq!(path.weights, vec![10, -8, 5]);
+        assert_eq!(path.cost, 7);
+    }
+
+    #[test]
+    fn negative_cycle() {
+        let v = vertices();
+        let edges = vec![
+            Edge::new("AB".into(), v[0].clone(), v[1].clone(), 1),
+            Edge::new("BC".into(), v[1].clone(), v[2].clone(), 2),
+            Edge::new("CD".into(), v[2].clone(), v[3].clone(), 1),
+            Edge::new("DB".into(), v[3].clone(), v[1].clone(), -4),
+        ];
+        let mut bellman_ford = BellmanFord::new(Graph::new(v.clone(), edges));
+        match bellman_ford.run(&v[0]) {
+            Err(DijkstraError::NegativeCycle(mut lanes)) => {
+                lanes.sort();
+                assert_eq!(lanes, vec!["BC", "CD", "DB"]);
+            }
+            other => panic!("expected negative cycle, got {:?}", other),
+        }
+        assert_eq!(
+            bellman_ford.get_route(&v[2]),
+            Err(DijkstraError::Unreachable("C".into()))
+        );
+        assert_eq!(
+            bellman_ford.get_path(&v[0]),
+            Err(DijkstraError::Unreachable("A".into()))
+        );
+        assert_eq!(
+            bellman_ford.run(&Vertex::new("X".into(), "X".into())),
+            Err(DijkstraError::UnknownSource("X".into()))
+        );
+
+        let edges = vec![
+            Edge::new("AB".into(), v[0].clone(), v[1].clone(), -1_000_000_000),
+            Edge::new("BC".into(), v[1].clone(), v[2].clone(), -1_000_000_000),
+            Edge::new("CA".into(), v[2].clone(), v[0].clone(), -1_000_000_000),
+        ];
+        let mut bellman_ford = BellmanFord::new(Graph::new(v.clone(), edges));
+        match bellman_ford.run(&v[0]) {
+            Err(DijkstraError::NegativeCycle(mut lanes)) => {
+                lanes.sort();
+                assert_eq!(lanes, vec!["AB", "BC", "CA"]);
+            }
+            other => panic!("expected negative cycle, got {:?}", other),
+        }
+        assert_eq!(
+            bellman_ford.get_route(&v[1]),
+            Err(DijkstraError::Unreachable("B".into()))
+        );
+    }
+}
@@ -0,0 +1,252 @@
+use crate::workspace::{State, Workspace};
+use crate::{CsrGraph, DijkstraError, Djikstra, GraphEdge, GraphVertex, Path, VertexIndex, Weight};
+
+type Meeting<W> = Option<(W, VertexIndex)>;
+
+impl<V: GraphVertex + Clone, E: GraphEdge + Clone> Djikstra<V, E> {
+    /// Returns a shortest route, but the configured `TieBreak` is not applied
+    /// where routes tie; use `run_to` when ties must resolve reproducibly.
+    pub fn run_bidirectional<S, T>(
+        &mut self,
+        source: &S,
+        target: &T,
+    ) -> Result<Path<V, E>, DijkstraError>
+    where
+        S: GraphVertex + ?Sized,
+        T: GraphVertex + ?Sized,
+    {
+        let vertex_count = self.graph.vertex_count();
+        self.forward.reset(vertex_count);
+        self.backward.reset(vertex_count);
+
+        if let Some(edge) = self.graph.negative_edge() {
+            return Err(DijkstraError::NegativeWeight(
+                self.graph.edge_id(edge).to_string(),
+            ));
+        }
+        let from = self
+            .vertex_index(source.id())
+            .ok_or_else(|| DijkstraError::UnknownSource(source.id().to_string()))?;
+        let to = self
+            .vertex_index(target.id())
+            .ok_or_else(|| DijkstraError::UnknownTarget(target.id().to_string()))?;
+        self.forward.start(from, E::Weight::zero());
+        self.backward.start(to, E::Weight::zero());
+
+        let mut best = if from == to {
+            Some((E::Weight::zero(), from))
+        } else {
+            None
+        };
+        while let (Some(forward), Some(backward)) =
+            (self.forward.min_cost(), self.backward.min_cost())
+        {
+            if let Some((cost, _)) = best {
+                match forward.checked_add(backward) {
+                    Some(sum) if sum < cost => {}
+                    _ => break,
+                }
+            }
+            if forward <= backward {
+                expand_forward(&self.graph, &mut self.forward, &self.backward, &mut best)?;
+            } else {
+                expand_backward(&self.graph, &mut self.backward, &self.forward, &mut best)?;
+            }
+        }
+
+        let (cost, meeting) =
+            best.ok_or_else(|| DijkstraError::Unreachable(target.id().to_string()))?;
+        Ok(Path::through(
+            &self.graph,
+            |vertex| self.forward.predecessor(vertex),
+            |vertex| self.backward.predecessor(vertex),
+            meeting,
+            cost,
+        ))
+    }
+}
+
+fn expand_forward<V: GraphVertex, E: GraphEdge>(
+    graph: &CsrGraph<V, E>,
+    frontier: &mut Workspace<E::Weight>,
+    other: &Workspace<E::Weight>,
+    best: &mut Meeting<E::Weight>,
+) -> Result<(), DijkstraError> {
+    if let Some(State { vertex: node, .. }) = frontier.pop_unsettled() {
+        frontier.settle(node);
+        for edge in graph.edge_range(node) {
+            let weight = graph.edge_weight(edge);
+            relax(
+                frontier,
+                other,
+                node,
+                graph.edge_target(edge),
+                edge,
+                weight,
+                best,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn expand_backward<V: GraphVertex, E: GraphEdge>(
+    graph: &CsrGraph<V, E>,
+    frontier: &mut Workspace<E::Weight>,
+    other: &Workspace<E::Weight>,
+    best: &mut Meeting<E::Weight>,
+) -> Result<(), DijkstraError> {
+    if let Some(State { vertex: node, .. }) = frontier.pop_unsettled() {
+        frontier.settle(node);
+        for &edge in graph.incoming(node) {
+            let weight = graph.edge_weight(edge);
+            relax(
+                frontier,
+                other,
+                node,
+                graph.edge_source(edge),
+                edge,
+                weight,
+                best,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn relax<W: Weight>(
+    frontier: &mut Workspace<W>,
+    other: &Workspace<W>,
+    node: VertexIndex,
+    neighbor: VertexIndex,
+    edge: usize,
+    weight: W,
+    best: &mut Meeting<W>,
+) -> Result<(), DijkstraError> {
+    if frontier.is_settled(neighbor) {
+        return Ok(());
+    }
+    let cost = frontier
+        .distance(node)
+        .checked_add(weight)
+        .ok_or(DijkstraError::Overflow)?;
+    if cost < frontier.distance(neighbor) {
+        frontier.improve(neighbor, cost, node, edge);
+        if let Some(total) = cost.checked_add(other.distance(neighbor)) {
+            match best {
+                Some((cost, _)) if total >= *cost => {}
+                _ => *best = Some((total, neighbor)),
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{add_lane, vertices};
+    use crate::{DijkstraError, Djikstra, Edge, Graph, TieBreak, Vertex};
+
+    fn grid(size: usize) -> (Vec<Vertex>, Vec<Edge>) {
+        let mut nodes = vec![];
+        let mut edges = vec![];
+        for i in 0..size * size {
+            nodes.push(Vertex::new(i.to_string(), i.to_string()));
+        }
+        for i in 0..size * size {
+            let weight = (i * 7 % 5 + 1) as i32;
+            if i % size + 1 < size {
+                edges.push(Edge::new(
+                    format!("{}>", i),
+                    nodes[i].clone(),
+                    nodes[i + 1].clone(),
+                    weight,
+                ));
+            }
+            if i + size < size * size {
+                edges.push(Edge::new(
+                    format!("{}v", i),
+                    nodes[i].clone(),
+                    nodes[i + size].clone(),
+                    weight + 1,
+                ));
+            }
+            if i >= size {
+                edges.push(Edge::new(
+                    format!("{}^", i),
+                    nodes[i].clone(),
+                    nodes[i - size].clone(),
+                    weight * 2,
+                ));
+            }
+        }
+        (nodes, edges)
+    }
+
+    #[test]
+    fn matches_unidirectional() {
+        let (nodes, edges) = grid(6);
+        let mut djikstra = Djikstra::new(Graph::new(nodes.clone(), edges));
+        for source in nodes.iter().step_by(5) {
+            for target in nodes.iter().step_by(3) {
+                let expected = djikstra.run_to(source, target).map(|path| path.cost);
+                let path = djikstra.run_bidirectional(source, target).map(|path| {
+                    assert_eq!(path.source(), source);
+                    assert_eq!(path.target(), target);
+                    assert_eq!(path.weights.iter().sum::<i32>(), path.cost);
+                    path.cost
+                });
+                assert_eq!(path, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn endpoints() {
+        let (nodes, edges) = grid(2);
+        let mut djikstra = Djikstra::new(Graph::new(nodes.clone(), edges));
+        let path = djikstra.run_bidirectional(&nodes[0], &nodes[3]).unwrap();
+        assert_eq!(path.vertex_ids(), vec!["0", "1", "3"]);
+        assert_eq!(path.edges, vec!["0>", "1v"]);
+        assert_eq!(path.cost, 5);
+
+        let path = djikstra.run_bidirectional(&nodes[2], &nodes[2]).unwrap();
+        assert_eq!(path.vertex_ids(), vec!["2"]);
+        assert_eq!(path.cost, 0);
+
+        assert_eq!(
+            djikstra.run_bidirectional(&nodes[1], &nodes[2]),
+            Err(DijkstraError::Unreachable("2".into()))
+        );
+        assert_eq!(
+            djikstra.run_bidirectional(&nodes[0], "X"),
+            Err(DijkstraError::UnknownTarget("X".into()))
+        );
+    }
+
+    #[test]
+    fn leaves_other_queries_alone() {
+        let nodes = vertices(&["A", "B", "C", "D", "T"]);
+        let mut edges = vec![];
+        add_lane(&nodes, &mut edges, "AB".into(), 0, 1, 1);
+        add_lane(&nodes, &mut edges, "BC".into(), 1, 2, 1);
+        add_lane(&nodes, &mut edges, "CT".into(), 2, 4, 1);
+        add_lane(&nodes, &mut edges, "AD".into(), 0, 3, 2);
+        add_lane(&nodes, &mut edges, "DT".into(), 3, 4, 1);
+        for &tie_break in &[
+            TieBreak::InsertionOrder,
+            TieBreak::FewestHops,
+            TieBreak::LowestVertexId,
+        ] {
+            let graph = Graph::new(nodes.clone(), edges.clone());
+            let mut djikstra = Djikstra::new(graph).with_tie_break(tie_break);
+            djikstra.run("A").unwrap();
+            let expected = djikstra.get_route("T").unwrap();
+
+            let path = djikstra.run_bidirectional("A", "T").unwrap();
+            assert_eq!(path.cost, 3);
+            assert_eq!(path.edges, vec!["AD", "DT"]);
+            assert_eq!(djikstra.get_route("T"), Ok(expected));
+        }
+    }
+}
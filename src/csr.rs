@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::{Direction, Edge, Graph, GraphEdge, GraphVertex, Vertex, VertexIndex, Weight};
+
+#[derive(Debug, Clone)]
+pub struct CsrGraph<V = Vertex, E = Edge>
+where
+    E: GraphEdge,
+{
+    vertices: Vec<V>,
+    index: HashMap<String, VertexIndex>,
+    offsets: Vec<usize>,
+    sources: Vec<VertexIndex>,
+    targets: Vec<VertexIndex>,
+    weights: Vec<E::Weight>,
+    edges: Vec<E>,
+    lanes: Vec<usize>,
+    order: Vec<usize>,
+    incoming_offsets: Vec<usize>,
+    incoming: Vec<usize>,
+    negative_edge: Option<usize>,
+}
+
+impl<V: GraphVertex, E: GraphEdge> CsrGraph<V, E> {
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    pub fn vertices(&self) -> &[V] {
+        &self.vertices
+    }
+
+    pub fn vertex_index(&self, id: &str) -> Option<VertexIndex> {
+        self.index.get(id).copied()
+    }
+
+    pub fn vertex(&self, index: VertexIndex) -> &V {
+        &self.vertices[index.index()]
+    }
+
+    pub fn edge_range(&self, source: VertexIndex) -> Range<usize> {
+        self.offsets[source.index()]..self.offsets[source.index() + 1]
+    }
+
+    pub fn targets(&self, source: VertexIndex) -> &[VertexIndex] {
+        &self.targets[self.edge_range(source)]
+    }
+
+    pub fn weights(&self, source: VertexIndex) -> &[E::Weight] {
+        &self.weights[self.edge_range(source)]
+    }
+
+    pub fn edge(&self, edge: usize) -> &E {
+        &self.edges[self.lanes[edge]]
+    }
+
+    pub fn edge_id(&self, edge: usize) -> &str {
+        self.edge(edge).id()
+    }
+
+    pub fn lane_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn lane(&self, edge: usize) -> usize {
+        self.lanes[edge]
+    }
+
+    pub fn incoming(&self, target: VertexIndex) -> &[usize] {
+        &self.incoming
+            [self.incoming_offsets[target.index()]..self.incoming_offsets[target.index() + 1]]
+    }
+
+    pub fn edge_source(&self, edge: usize) -> VertexIndex {
+        self.sources[edge]
+    }
+
+    pub fn edge_order(&self, edge: usize) -> usize {
+        self.order[edge]
+    }
+
+    pub fn edge_target(&self, edge: usize) -> VertexIndex {
+        self.targets[edge]
+    }
+
+    pub fn edge_weight(&self, edge: usize) -> E::Weight {
+        self.weights[edge]
+    }
+
+    pub fn negative_edge(&self) -> Option<usize> {
+        self.negative_edge
+    }
+
+    pub fn to_graph(&self) -> Graph<V, E>
+    where
+        V: Clone,
+        E: Clone,
+    {
+        Graph::new(self.vertices.clone(), self.edges.clone())
+    }
+}
+
+// Edges whose endpoints are not registered vertices are left out; run
+// `Graph::validate` first to have them reported instead.
+impl<V: GraphVertex, E: GraphEdge> From<Graph<V, E>> for CsrGraph<V, E> {
+    fn from(graph: Graph<V, E>) -> Self {
+        let mut vertices = Vec::with_capacity(graph.vertices.len());
+        let mut index = HashMap::with_capacity(graph.vertices.len());
+        for vertex in graph.vertices {
+            if !index.contains_key(vertex.id()) {
+                index.insert(vertex.id().to_string(), VertexIndex(vertices.len() as u32));
+                vertices.push(vertex);
+            }
+        }
+        let mut edges = Vec::with_capacity(graph.edges.len());
+        let mut arcs = Vec::with_capacity(graph.edges.len());
+        for (position, edge) in graph.edges.into_iter().enumerate() {
+            let (source, destination) =
+                match (index.get(edge.source()), index.get(edge.destination())) {
+                    (Some(&source), Some(&destination)) => (source, destination),
+                    _ => continue,
+                };
+            let lane = edges.len();
+            arcs.push((source, destination, edge.weight(), position, lane));
+            match edge.direction() {
+                Direction::Directed => {}
+                Direction::Symmetric => {
+                    arcs.push((destination, source, edge.weight(), position, lane))
+                }
+                Direction::Asymmetric { backward } => {
+                    arcs.push((destination, source, backward, position, lane))
+                }
+            }
+            edges.push(edge);
+        }
+
+        let mut offsets = vec![0; vertices.len() + 1];
+        for (source, _, _, _, _) in &arcs {
+            offsets[source.index() + 1] += 1;
+        }
+        for i in 1..offsets.len() {
+            offsets[i] += offsets[i - 1];
+        }
+
+        let mut next = offsets.clone();
+        let mut slots = vec![None; arcs.len()];
+        for (source, destination, weight, position, lane) in arcs {
+            slots[next[source.index()]] = Some((destination, weight, position, lane));
+            next[source.index()] += 1;
+        }
+
+        let mut sources = Vec::with_capacity(slots.len());
+        for source in 0..vertices.len() {
+            sources.resize(offsets[source + 1], VertexIndex(source as u32));
+        }
+
+        let mut targets = Vec::with_capacity(slots.len());
+        let mut weights = Vec::with_capacity(slots.len());
+        let mut lanes = Vec::with_capacity(slots.len());
+        let mut order = Vec::with_capacity(slots.len());
+        for (destination, weight, position, lane) in slots.into_iter().flatten() {
+            targets.push(destination);
+            weights.push(weight);
+            lanes.push(lane);
+            order.push(position);
+        }
+
+        let mut incoming_offsets = vec![0; vertices.len() + 1];
+        for target in &targets {
+            incoming_offsets[target.index() + 1] += 1;
+        }
+        for i in 1..incoming_offsets.len() {
+            incoming_offsets[i] += incoming_offsets[i - 1];
+        }
+        let mut next = incoming_offsets.clone();
+        let mut incoming = vec![0; targets.len()];
+        for (edge, target) in targets.iter().enumerate() {
+            incoming[next[target.index()]] = edge;
+            next[target.index()] += 1;
+        }
+
+        let negative_edge = weights.iter().position(|weight| weight.is_negative());
+        CsrGraph {
+            vertices,
+            index,
+            offsets,
+            sources,
+            targets,
+            weights,
+            edges,
+            lanes,
+            order,
+            incoming_offsets,
+            incoming,
+            negative_edge,
+        }
+    }
+}
+
+impl<V: GraphVertex + Clone, E: GraphEdge + Clone> From<&CsrGraph<V, E>> for Graph<V, E> {
+    fn from(graph: &CsrGraph<V, E>) -> Self {
+        graph.to_graph()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let a = Vertex::new("A".into(), "A".into());
+        let b = Vertex::new("B".into(), "B".into());
+        let c = Vertex::new("C".into(), "C".into());
+        let graph = Graph::new(
+            vec![a.clone(), b.clone(), c.clone()],
+            vec![
+                Edge::new("BC".into(), b.clone(), c.clone(), 3),
+                Edge::new("AB".into(), a.clone(), b.clone(), 1),
+                Edge::new("AC".into(), a.clone(), c.clone(), 5),
+            ],
+        );
+        let csr = CsrGraph::from(graph);
+        assert_eq!(csr.vertex_count(), 3);
+        assert_eq!(csr.edge_count(), 3);
+
+        let source = csr.vertex_index("A").unwrap();
+        assert_eq!(csr.targets(source), &[VertexIndex(1), VertexIndex(2)]);
+        assert_eq!(csr.weights(source), &[1, 5]);
+        assert!(csr.targets(VertexIndex(2)).is_empty());
+        let into_c: Vec<_> = csr
+            .incoming(VertexIndex(2))
+            .iter()
+            .map(|&edge| (csr.edge_source(edge), csr.edge_id(edge)))
+            .collect();
+        assert_eq!(into_c, vec![(VertexIndex(0), "AC"), (VertexIndex(1), "BC")]);
+        assert!(csr.incoming(source).is_empty());
+
+        let graph = csr.to_graph();
+        assert_eq!(graph.vertices, vec![a.clone(), b.clone(), c.clone()]);
+        let ids: Vec<_> = graph.edges.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["BC", "AB", "AC"]);
+        assert_eq!(graph.edges[0], Edge::new("BC".into(), b, c, 3));
+    }
+
+    #[test]
+    fn round_trip_keeps_insertion_order() {
+        let nodes: Vec<_> = ["A", "B", "T"]
+            .iter()
+            .map(|id| Vertex::new(id.to_string(), id.to_string()))
+            .collect();
+        let graph = Graph::new(
+            nodes.clone(),
+            vec![
+                Edge::new("BT".into(), nodes[1].clone(), nodes[2].clone(), 1),
+                Edge::new("AT2".into(), nodes[0].clone(), nodes[2].clone(), 2),
+                Edge::new("AB".into(), nodes[0].clone(), nodes[1].clone(), 1),
+            ],
+        );
+        let round_tripped = CsrGraph::from(graph.clone()).to_graph();
+        assert_eq!(round_tripped.edges, graph.edges);
+
+        let original = crate::Djikstra::new(graph).run_to("A", "T").unwrap();
+        let copy = crate::Djikstra::new(round_tripped)
+            .run_to("A", "T")
+            .unwrap();
+        assert_eq!(original.edges, copy.edges);
+        assert_eq!(original.edges, vec!["AB", "BT"]);
+    }
+}
@@ -0,0 +1,197 @@
+use crate::{DijkstraError, Djikstra, GraphEdge, GraphVertex, Path, VertexIndex};
+
+impl<V: GraphVertex + Clone, E: GraphEdge + Clone> Djikstra<V, E> {
+    pub fn optimal_predecessors_of<T: GraphVertex + ?Sized>(
+        &self,
+        target: &T,
+    ) -> Result<impl Iterator<Item = (&V, &E)> + '_, DijkstraError> {
+        let index = self.reached_index(target)?;
+        Ok(self
+            .workspace
+            .optimal_predecessors(index)
+            .iter()
+            .map(move |&(previous, edge)| (self.vertex(previous), self.graph.edge(edge))))
+    }
+
+    pub fn count_shortest_paths<T: GraphVertex + ?Sized>(
+        &self,
+        target: &T,
+    ) -> Result<u64, DijkstraError> {
+        let target = self.reached_index(target)?;
+        let mut counts: Vec<Option<u64>> = vec![None; self.graph.vertex_count()];
+        let mut stack = vec![(target, false)];
+        while let Some((vertex, expanded)) = stack.pop() {
+            if counts[vertex.index()].is_some() {
+                continue;
+            }
+            let predecessors = self.workspace.optimal_predecessors(vertex);
+            if !expanded {
+                stack.push((vertex, true));
+                stack.extend(
+                    predecessors
+                        .iter()
+                        .filter(|&&(previous, _)| counts[previous.index()].is_none())
+                        .map(|&(previous, _)| (previous, false)),
+                );
+                continue;
+            }
+            // A source keeps no predecessor, yet may also be reached through
+            // another source at the same cost; it counts as a route of its own.
+            let own = self.workspace.predecessor(vertex).is_none() as u64;
+            let count = predecessors
+                .iter()
+                .try_fold(own, |total, &(previous, _)| {
+                    total.checked_add(counts[previous.index()].unwrap_or(0))
+                })
+                .ok_or(DijkstraError::Overflow)?;
+            counts[vertex.index()] = Some(count);
+        }
+        Ok(counts[target.index()].unwrap_or(0))
+    }
+
+    pub fn shortest_paths<T: GraphVertex + ?Sized>(
+        &self,
+        target: &T,
+    ) -> Result<impl Iterator<Item = Path<V, E>> + '_, DijkstraError> {
+        let target = self.reached_index(target)?;
+        let cost = self.workspace.distance(target);
+        let mut stack = vec![(target, 0)];
+        self.descend(&mut stack);
+
+        Ok(std::iter::from_fn(move || {
+            let &(source, _) = stack.last()?;
+            let arcs: Vec<usize> = stack
+                .iter()
+                .rev()
+                .skip(1)
+                .map(|&(vertex, choice)| self.workspace.optimal_predecessors(vertex)[choice].1)
+                .collect();
+            let path = Path::from_arcs(&self.graph, source, &arcs, cost);
+
+            stack.pop();
+            while let Some((vertex, choice)) = stack.pop() {
+                if choice + 1 < self.choices(vertex) {
+                    stack.push((vertex, choice + 1));
+                    self.descend(&mut stack);
+                    break;
+                }
+            }
+            Some(path)
+        }))
+    }
+
+    fn descend(&self, stack: &mut Vec<(VertexIndex, usize)>) {
+        while let Some(&(vertex, choice)) = stack.last() {
+            match self.workspace.optimal_predecessors(vertex).get(choice) {
+                Some(&(previous, _)) => stack.push((previous, 0)),
+                None => break,
+            }
+        }
+    }
+
+    fn choices(&self, vertex: VertexIndex) -> usize {
+        let own = self.workspace.predecessor(vertex).is_none() as usize;
+        self.workspace.optimal_predecessors(vertex).len() + own
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{add_lane, vertices};
+    use crate::{DijkstraError, Djikstra, Graph};
+
+    #[test]
+    fn counts_and_enumerates_ties() {
+        let nodes = vertices(&["A", "B", "C", "D", "E", "F", "T", "W"]);
+        let mut edges = vec![];
+        add_lane(&nodes, &mut edges, "AB".into(), 0, 1, 1);
+        add_lane(&nodes, &mut edges, "AC".into(), 0, 2, 1);
+        add_lane(&nodes, &mut edges, "BD".into(), 1, 3, 1);
+        add_lane(&nodes, &mut edges, "CD".into(), 2, 3, 1);
+        add_lane(&nodes, &mut edges, "AD".into(), 0, 3, 3);
+        add_lane(&nodes, &mut edges, "DE1".into(), 3, 4, 2);
+        add_lane(&nodes, &mut edges, "DE2".into(), 3, 4, 2);
+        add_lane(&nodes, &mut edges, "DF".into(), 3, 5, 1);
+        add_lane(&nodes, &mut edges, "AW".into(), 0, 7, 1);
+        add_lane(&nodes, &mut edges, "AT".into(), 0, 6, 1);
+        add_lane(&nodes, &mut edges, "WT".into(), 7, 6, 0);
+        let mut djikstra = Djikstra::new(Graph::new(nodes, edges));
+        djikstra.run("A").unwrap();
+
+        assert_eq!(djikstra.count_shortest_paths("A"), Ok(1));
+        assert_eq!(djikstra.count_shortest_paths("D"), Ok(2));
+        assert_eq!(djikstra.count_shortest_paths("E"), Ok(4));
+        let into_d: Vec<_> = djikstra
+            .optimal_predecessors_of("D")
+            .unwrap()
+            .map(|(vertex, lane)| (vertex.id.as_str(), lane.id.as_str()))
+            .collect();
+        assert_eq!(into_d, vec![("B", "BD"), ("C", "CD")]);
+
+        let mut routes: Vec<_> = djikstra
+            .shortest_paths("E")
+            .unwrap()
+            .map(|path| {
+                assert_eq!(path.cost, 4);
+                path.edges
+            })
+            .collect();
+        routes.sort();
+        assert_eq!(
+            routes,
+            vec![
+                vec!["AB", "BD", "DE1"],
+                vec!["AB", "BD", "DE2"],
+                vec!["AC", "CD", "DE1"],
+                vec!["AC", "CD", "DE2"],
+            ]
+        );
+        assert_eq!(djikstra.shortest_paths("F").unwrap().take(1).count(), 1);
+
+        assert_eq!(djikstra.count_shortest_paths("T"), Ok(2));
+        let mut routes: Vec<_> = djikstra
+            .shortest_paths("T")
+            .unwrap()
+            .map(|path| path.edges)
+            .collect();
+        routes.sort();
+        assert_eq!(routes, vec![vec!["AT"], vec!["AW", "WT"]]);
+        let route = djikstra.run_to("A", "T").unwrap();
+        assert_eq!(route.cost, 1);
+        assert_eq!(djikstra.count_shortest_paths("T"), Ok(2));
+        let only: Vec<_> = djikstra.shortest_paths("A").unwrap().collect();
+        assert_eq!(only.len(), 1);
+        assert_eq!(only[0].vertex_ids(), vec!["A"]);
+        assert!(djikstra.shortest_paths("X").is_err());
+        assert_eq!(
+            djikstra.count_shortest_paths("X"),
+            Err(DijkstraError::UnknownTarget("X".into()))
+        );
+    }
+
+    #[test]
+    fn seeded_sources_are_roots() {
+        let nodes = vertices(&["D1", "D2", "X"]);
+        let mut edges = vec![];
+        add_lane(&nodes, &mut edges, "D1D2".into(), 0, 1, 3);
+        add_lane(&nodes, &mut edges, "D2X".into(), 1, 2, 1);
+        let mut djikstra = Djikstra::new(Graph::new(nodes, edges));
+        djikstra
+            .run_from_sources(vec![("D1", 0), ("D2", 3)])
+            .unwrap();
+
+        assert_eq!(djikstra.get_path("X").unwrap(), vec!["D2", "X"]);
+        assert_eq!(djikstra.count_shortest_paths("D2"), Ok(2));
+        assert_eq!(djikstra.count_shortest_paths("X"), Ok(2));
+        let mut routes: Vec<_> = djikstra
+            .shortest_paths("X")
+            .unwrap()
+            .map(|path| {
+                assert_eq!(path.cost, 4);
+                path.vertex_ids()
+            })
+            .collect();
+        routes.sort();
+        assert_eq!(routes, vec![vec!["D1", "D2", "X"], vec!["D2", "X"]]);
+    }
+}
@@ -0,0 +1,35 @@
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DijkstraError {
+    UnknownSource(String),
+    UnknownTarget(String),
+    Unreachable(String),
+    InvalidGraph(String),
+    NegativeWeight(String),
+    NegativeCycle(Vec<String>),
+    NegativeOffset(String),
+    Overflow,
+}
+
+impl fmt::Display for DijkstraError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DijkstraError::UnknownSource(id) => write!(f, "unknown source vertex `{}`", id),
+            DijkstraError::UnknownTarget(id) => write!(f, "unknown target vertex `{}`", id),
+            DijkstraError::Unreachable(id) => write!(f, "vertex `{}` is unreachable", id),
+            DijkstraError::InvalidGraph(reason) => write!(f, "invalid graph: {}", reason),
+            DijkstraError::NegativeWeight(id) => write!(f, "lane `{}` has a negative weight", id),
+            DijkstraError::NegativeCycle(lanes) => {
+                write!(f, "negative cycle through lanes {}", lanes.join(", "))
+            }
+            DijkstraError::NegativeOffset(id) => {
+                write!(f, "source `{}` has a negative offset", id)
+            }
+            DijkstraError::Overflow => write!(f, "path cost overflowed"),
+        }
+    }
+}
+
+impl Error for DijkstraError {}
@@ -0,0 +1,83 @@
+use std::hash::{Hash, Hasher};
+
+pub const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Coordinates {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl Coordinates {
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        Coordinates {
+            latitude,
+            longitude,
+        }
+    }
+
+    pub fn distance_km(&self, other: &Coordinates) -> f64 {
+        haversine_km(*self, *other)
+    }
+
+    pub(crate) fn to_unit_vector(self) -> [f64; 3] {
+        let (latitude, longitude) = (self.latitude.to_radians(), self.longitude.to_radians());
+        [
+            latitude.cos() * longitude.cos(),
+            latitude.cos() * longitude.sin(),
+            latitude.sin(),
+        ]
+    }
+}
+
+impl PartialEq for Coordinates {
+    fn eq(&self, other: &Self) -> bool {
+        self.latitude.to_bits() == other.latitude.to_bits()
+            && self.longitude.to_bits() == other.longitude.to_bits()
+    }
+}
+
+impl Eq for Coordinates {}
+
+impl Hash for Coordinates {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.latitude.to_bits().hash(state);
+        self.longitude.to_bits().hash(state);
+    }
+}
+
+pub trait Located {
+    fn coordinates(&self) -> Option<Coordinates>;
+}
+
+pub fn haversine_km(from: Coordinates, to: Coordinates) -> f64 {
+    let (lat1, lon1) = (from.latitude.to_radians(), from.longitude.to_radians());
+    let (lat2, lon2) = (to.latitude.to_radians(), to.longitude.to_radians());
+    let a = ((lat2 - lat1) / 2.0).sin().powi(2)
+        + lat1.cos() * lat2.cos() * ((lon2 - lon1) / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * a.sqrt().min(1.0).asin()
+}
+
+pub(crate) fn chord_to_km(chord: f64) -> f64 {
+    2.0 * EARTH_RADIUS_KM * (chord / 2.0).min(1.0).asin()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_distances() {
+        let hamburg = Coordinates::new(53.5511, 9.9937);
+        let munich = Coordinates::new(48.1351, 11.5820);
+        assert!((haversine_km(hamburg, munich) - 612.0).abs() < 2.0);
+        assert_eq!(hamburg.distance_km(&hamburg), 0.0);
+        let null_island = Coordinates::new(0.0, 0.0);
+        assert!((null_island.distance_km(&Coordinates::new(0.0, 180.0)) - 20015.1).abs() < 1.0);
+
+        let [x, y, z] = hamburg.to_unit_vector();
+        let [u, v, w] = munich.to_unit_vector();
+        let chord = ((x - u).powi(2) + (y - v).powi(2) + (z - w).powi(2)).sqrt();
+        assert!((chord_to_km(chord) - haversine_km(hamburg, munich)).abs() < 1e-6);
+    }
+}
@@ -1,171 +1,391 @@
-use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+mod astar;
+mod bellman_ford;
+mod bidirectional;
+mod csr;
+mod equal_paths;
+mod error;
+mod geo;
+mod path;
+mod payload;
+mod reverse;
+mod spatial;
+mod tie_break;
+mod validate;
+mod weight;
+mod workspace;
+mod yen;
+
+pub use astar::{AStar, FromEstimate, GreatCircle, Heuristic, NoHeuristic};
+pub use bellman_ford::BellmanFord;
+pub use csr::CsrGraph;
+pub use error::DijkstraError;
+pub use geo::{haversine_km, Coordinates, Located, EARTH_RADIUS_KM};
+pub use path::Path;
+pub use payload::{GraphEdge, GraphVertex};
+pub use reverse::Reversed;
+pub use spatial::SpatialIndex;
+pub use tie_break::TieBreak;
+pub use validate::GraphProblem;
+pub use weight::{TotalF64, Weight};
+
+use workspace::{State, Workspace};
+
+type Band<'a, V, W> = (W, Vec<&'a V>);
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct Vertex {
     pub id: String,
     pub name: String,
+    pub coordinates: Option<Coordinates>,
 }
 
 impl Vertex {
     pub fn new(id: String, name: String) -> Self {
-        Vertex { id, name }
+        Vertex {
+            id,
+            name,
+            coordinates: None,
+        }
+    }
+
+    pub fn with_coordinates(mut self, latitude: f64, longitude: f64) -> Self {
+        self.coordinates = Some(Coordinates::new(latitude, longitude));
+        self
+    }
+}
+
+impl Located for Vertex {
+    fn coordinates(&self) -> Option<Coordinates> {
+        self.coordinates
+    }
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct VertexIndex(pub u32);
+
+impl VertexIndex {
+    pub fn index(self) -> usize {
+        self.0 as usize
     }
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
-pub struct Edge {
+pub struct Edge<W = i32> {
     pub id: String,
     pub source: Vertex,
     pub destination: Vertex,
-    pub weight: i32,
+    pub weight: W,
+    pub direction: Direction<W>,
 }
 
-impl Edge {
-    pub fn new(id: String, source: Vertex, destination: Vertex, weight: i32) -> Self {
+impl<W> Edge<W> {
+    pub fn new(id: String, source: Vertex, destination: Vertex, weight: W) -> Self {
         Edge {
             id,
             source,
             destination,
             weight,
+            direction: Direction::Directed,
         }
     }
+
+    pub fn with_direction(mut self, direction: Direction<W>) -> Self {
+        self.direction = direction;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Default)]
+pub enum Direction<W> {
+    #[default]
+    Directed,
+    Symmetric,
+    Asymmetric {
+        backward: W,
+    },
 }
 
 #[derive(Debug, Clone, Hash)]
-pub struct Graph {
-    pub vertices: Vec<Vertex>,
-    pub edges: Vec<Edge>,
+pub struct Graph<V = Vertex, E = Edge> {
+    pub vertices: Vec<V>,
+    pub edges: Vec<E>,
 }
-impl Graph {
-    pub fn new(vertices: Vec<Vertex>, edges: Vec<Edge>) -> Self {
+impl<V, E> Graph<V, E> {
+    pub fn new(vertices: Vec<V>, edges: Vec<E>) -> Self {
         Graph { vertices, edges }
     }
 }
 
-#[derive(Debug)]
-pub struct Djikstra {
-    nodes: Vec<Vertex>,
-    edges: Vec<Edge>,
-    settled_nodes: HashSet<Vertex>,
-    unsettled_nodes: HashSet<Vertex>,
-    predecessors: HashMap<String, Vertex>,
-    distance: HashMap<String, i32>,
+#[derive(Debug, Clone)]
+pub struct Djikstra<V = Vertex, E = Edge>
+where
+    E: GraphEdge,
+{
+    graph: Arc<CsrGraph<V, E>>,
+    tie_break: TieBreak,
+    workspace: Workspace<E::Weight>,
+    forward: Workspace<E::Weight>,
+    backward: Workspace<E::Weight>,
+    reverse: Workspace<E::Weight>,
 }
 
-impl Djikstra {
-    pub fn new(graph: Graph) -> Self {
+impl<V: GraphVertex + Clone, E: GraphEdge + Clone> Djikstra<V, E> {
+    pub fn new(graph: Graph<V, E>) -> Self {
+        Self::from_csr(CsrGraph::from(graph))
+    }
+
+    pub fn from_csr(graph: CsrGraph<V, E>) -> Self {
+        Self::from_shared(Arc::new(graph))
+    }
+
+    pub fn from_shared(graph: Arc<CsrGraph<V, E>>) -> Self {
         Self {
-            nodes: graph.vertices,
-            edges: graph.edges,
-            settled_nodes: HashSet::new(),
-            unsettled_nodes: HashSet::new(),
-            predecessors: HashMap::new(),
-            distance: HashMap::new(),
-        }
-    }
-
-    pub fn run(&mut self, source: &Vertex) {
-        self.settled_nodes = HashSet::new();
-        self.unsettled_nodes = HashSet::new();
-        self.distance = HashMap::new();
-        self.predecessors = HashMap::new();
-
-        self.distance.insert(source.id.clone(), 0);
-        self.unsettled_nodes.insert(source.clone());
-
-        while self.unsettled_nodes.len() > 0 {
-            if let Some(node) = self.get_minimum(&self.unsettled_nodes) {
-                self.settled_nodes.insert(node.clone());
-                self.unsettled_nodes.remove(&node);
-                self.find_minimal_distance(&node);
-            } else {
-                panic!("Error");
-            }
+            graph,
+            tie_break: TieBreak::default(),
+            workspace: Workspace::new(),
+            forward: Workspace::new(),
+            backward: Workspace::new(),
+            reverse: Workspace::new(),
         }
     }
 
-    fn find_minimal_distance(&mut self, node: &Vertex) {
-        let adjacent_nodes = self.get_neighbors(node);
-        for target in &adjacent_nodes {
-            if self.get_shortest_distance(target)
-                > self.get_shortest_distance(node) + self.get_distance(node, target)
-            {
-                self.distance.insert(
-                    target.id.clone(),
-                    self.get_shortest_distance(node) + self.get_distance(node, target),
-                );
-                self.predecessors.insert(target.id.clone(), node.clone());
-                self.unsettled_nodes.insert(target.clone());
-            }
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    pub fn graph(&self) -> &CsrGraph<V, E> {
+        &self.graph
+    }
+
+    pub fn shared(&self) -> Arc<CsrGraph<V, E>> {
+        Arc::clone(&self.graph)
+    }
+
+    pub fn vertex_index(&self, id: &str) -> Option<VertexIndex> {
+        self.graph.vertex_index(id)
+    }
+
+    pub fn vertex(&self, index: VertexIndex) -> &V {
+        self.graph.vertex(index)
+    }
+
+    pub fn run<S: GraphVertex + ?Sized>(&mut self, source: &S) -> Result<(), DijkstraError> {
+        self.search(&[(source.id(), E::Weight::zero())], None, None)
+    }
+
+    pub fn run_from_sources<'a, S, I>(&mut self, sources: I) -> Result<(), DijkstraError>
+    where
+        S: GraphVertex + ?Sized + 'a,
+        I: IntoIterator<Item = (&'a S, E::Weight)>,
+    {
+        let sources: Vec<_> = sources
+            .into_iter()
+            .map(|(source, offset)| (source.id(), offset))
+            .collect();
+        self.search(&sources, None, None)
+    }
+
+    pub fn run_within<S: GraphVertex + ?Sized>(
+        &mut self,
+        source: &S,
+        budget: E::Weight,
+    ) -> Result<(), DijkstraError> {
+        self.search(&[(source.id(), E::Weight::zero())], None, Some(budget))
+    }
+
+    pub fn isochrones<S: GraphVertex + ?Sized>(
+        &mut self,
+        source: &S,
+        bands: &[E::Weight],
+    ) -> Result<Vec<Band<'_, V, E::Weight>>, DijkstraError> {
+        let mut bands = bands.to_vec();
+        bands.sort();
+        bands.dedup();
+        let budget = match bands.last() {
+            Some(&budget) => budget,
+            None => return Ok(vec![]),
+        };
+        self.run_within(source, budget)?;
+
+        let mut isochrones: Vec<_> = bands.into_iter().map(|band| (band, vec![])).collect();
+        for (vertex, cost) in self.reached() {
+            let band = isochrones.partition_point(|&(band, _)| band < cost);
+            isochrones[band].1.push(vertex);
         }
+        Ok(isochrones)
     }
 
-    fn get_distance(&self, node: &Vertex, target: &Vertex) -> i32 {
-        let mut weight = 0;
-        for edge in &self.edges {
-            if edge.source.id == node.id && edge.destination.id == target.id {
-                weight = edge.weight;
-            }
+    pub fn run_to<S, T>(&mut self, source: &S, target: &T) -> Result<Path<V, E>, DijkstraError>
+    where
+        S: GraphVertex + ?Sized,
+        T: GraphVertex + ?Sized,
+    {
+        let index = self
+            .vertex_index(target.id())
+            .ok_or_else(|| DijkstraError::UnknownTarget(target.id().to_string()))?;
+        self.search(&[(source.id(), E::Weight::zero())], Some(index), None)?;
+        if !self.is_settled(index) {
+            return Err(DijkstraError::Unreachable(target.id().to_string()));
         }
-        weight
+        Ok(self.route_to(index))
     }
 
-    fn get_neighbors(&self, node: &Vertex) -> Vec<Vertex> {
-        let mut neighbors = vec![];
-        for edge in &self.edges {
-            if edge.source.id == node.id && !self.is_settled(&edge.destination) {
-                neighbors.push(edge.clone().destination);
+    fn search(
+        &mut self,
+        sources: &[(&str, E::Weight)],
+        target: Option<VertexIndex>,
+        budget: Option<E::Weight>,
+    ) -> Result<(), DijkstraError> {
+        self.workspace.reset(self.graph.vertex_count());
+
+        if let Some(edge) = self.graph.negative_edge() {
+            return Err(DijkstraError::NegativeWeight(
+                self.graph.edge_id(edge).to_string(),
+            ));
+        }
+        for &(source, offset) in sources {
+            let source = self
+                .vertex_index(source)
+                .ok_or_else(|| DijkstraError::UnknownSource(source.to_string()))?;
+            if offset.is_negative() {
+                return Err(DijkstraError::NegativeOffset(
+                    self.vertex(source).id().to_string(),
+                ));
+            }
+            if offset < self.get_shortest_distance(source) {
+                self.workspace.start(source, offset);
+            }
+        }
+
+        // Vertices tied with the target are still expanded: a zero-weight lane
+        // from one of them may be another optimal way in.
+        while let Some(State { cost, vertex }) = self.workspace.pop_unsettled() {
+            let past_target = target.is_some_and(|target| {
+                self.is_settled(target) && cost > self.get_shortest_distance(target)
+            });
+            if past_target || budget.is_some_and(|budget| cost > budget) {
+                break;
             }
+            self.workspace.settle(vertex);
+            self.find_minimal_distance(vertex)?;
         }
-        neighbors
+        Ok(())
     }
 
-    fn get_minimum(&self, vertices: &HashSet<Vertex>) -> Option<Vertex> {
-        let mut minimum = None;
-        for vertex in vertices {
-            if minimum == None {
-                minimum = Some(vertex.clone());
-            } else if self.get_shortest_distance(vertex)
-                < self.get_shortest_distance(&minimum.clone().unwrap())
-            {
-                minimum = Some(vertex.clone());
+    fn find_minimal_distance(&mut self, node: VertexIndex) -> Result<(), DijkstraError> {
+        for edge in self.graph.edge_range(node) {
+            let target = self.graph.edge_target(edge);
+            let cost = self
+                .get_shortest_distance(node)
+                .checked_add(self.graph.edge_weight(edge));
+            if self.is_settled(target) {
+                if cost == Some(self.get_shortest_distance(target)) {
+                    self.tie_break
+                        .tie(&self.graph, &mut self.workspace, node, edge, target);
+                }
+                continue;
+            }
+            let cost = cost.ok_or(DijkstraError::Overflow)?;
+            let current = self.get_shortest_distance(target);
+            if current > cost {
+                self.workspace.improve(target, cost, node, edge);
+            } else if current == cost {
+                self.tie_break
+                    .tie(&self.graph, &mut self.workspace, node, edge, target);
             }
         }
-        return minimum;
+        Ok(())
     }
 
-    fn is_settled(&self, vertex: &Vertex) -> bool {
-        self.settled_nodes.contains(vertex)
+    fn is_settled(&self, vertex: VertexIndex) -> bool {
+        self.workspace.is_settled(vertex)
     }
 
-    fn get_shortest_distance(&self, destination: &Vertex) -> i32 {
-        if let Some(d) = self.distance.get(&destination.id) {
-            *d
-        } else {
-            std::i32::MAX
-        }
+    fn get_shortest_distance(&self, destination: VertexIndex) -> E::Weight {
+        self.workspace.distance(destination)
     }
 
-    pub fn get_path(&self, target: &Vertex) -> Vec<String> {
-        let mut path = vec![];
-        let mut step = target.clone();
+    pub fn get_path<T: GraphVertex + ?Sized>(
+        &self,
+        target: &T,
+    ) -> Result<Vec<String>, DijkstraError> {
+        self.get_route(target).map(|path| path.vertex_ids())
+    }
 
-        if self.predecessors.get(&step.id).is_none() {
-            return vec![];
-        } else {
-            path.push(step.id.clone());
-            while self.predecessors.get(&step.id.clone()).is_some() {
-                if let Some(s) = self.predecessors.get(&step.id.clone()) {
-                    step = s.clone();
-                    path.push(step.id.clone());
-                } else {
-                    break;
-                }
-            }
+    pub fn get_route<T: GraphVertex + ?Sized>(
+        &self,
+        target: &T,
+    ) -> Result<Path<V, E>, DijkstraError> {
+        let index = self.reached_index(target)?;
+        Ok(self.route_to(index))
+    }
+
+    pub fn distance_to<T: GraphVertex + ?Sized>(
+        &self,
+        target: &T,
+    ) -> Result<E::Weight, DijkstraError> {
+        let index = self.reached_index(target)?;
+        Ok(self.get_shortest_distance(index))
+    }
+
+    pub fn predecessor_of<T: GraphVertex + ?Sized>(
+        &self,
+        target: &T,
+    ) -> Result<Option<(&V, &E)>, DijkstraError> {
+        let index = self.reached_index(target)?;
+        Ok(self
+            .workspace
+            .predecessor(index)
+            .map(|(previous, edge)| (self.vertex(previous), self.graph.edge(edge))))
+    }
+
+    pub fn origin_of<T: GraphVertex + ?Sized>(&self, target: &T) -> Result<&V, DijkstraError> {
+        let index = self.reached_index(target)?;
+        Ok(self.vertex(self.workspace.origin(index).unwrap_or(index)))
+    }
+
+    pub fn reached(&self) -> impl Iterator<Item = (&V, E::Weight)> + '_ {
+        self.workspace
+            .settled()
+            .iter()
+            .map(move |&vertex| (self.vertex(vertex), self.get_shortest_distance(vertex)))
+    }
+
+    pub fn predecessor_tree(&self) -> impl Iterator<Item = (&V, &V, &E)> + '_ {
+        self.workspace.settled().iter().filter_map(move |&vertex| {
+            self.workspace.predecessor(vertex).map(|(previous, edge)| {
+                (
+                    self.vertex(vertex),
+                    self.vertex(previous),
+                    self.graph.edge(edge),
+                )
+            })
+        })
+    }
+
+    fn reached_index<T: GraphVertex + ?Sized>(
+        &self,
+        target: &T,
+    ) -> Result<VertexIndex, DijkstraError> {
+        let index = self
+            .vertex_index(target.id())
+            .ok_or_else(|| DijkstraError::UnknownTarget(target.id().to_string()))?;
+        if !self.is_settled(index) {
+            return Err(DijkstraError::Unreachable(target.id().to_string()));
         }
+        Ok(index)
+    }
 
-        path.reverse();
-        path
+    fn route_to(&self, target: VertexIndex) -> Path<V, E> {
+        Path::from_predecessors(
+            &self.graph,
+            |vertex| self.workspace.predecessor(vertex),
+            target,
+            self.get_shortest_distance(target),
+        )
     }
 }
 
@@ -173,8 +393,14 @@ impl Djikstra {
 mod tests {
     use super::*;
 
-    fn add_lane(
-        nodes: &Vec<Vertex>,
+    pub(crate) fn vertices(ids: &[&str]) -> Vec<Vertex> {
+        ids.iter()
+            .map(|id| Vertex::new(id.to_string(), id.to_string()))
+            .collect()
+    }
+
+    pub(crate) fn add_lane(
+        nodes: &[Vertex],
         edges: &mut Vec<Edge>,
         lane_id: String,
         source_loc_no: usize,
@@ -189,6 +415,7 @@ mod tests {
         );
         edges.push(e);
     }
+
     #[test]
     fn simple() {
         let mut nodes = vec![];
@@ -212,9 +439,9 @@ mod tests {
         let end = nodes[4].clone();
         let graph = Graph::new(nodes, edges);
         let mut djikstra = Djikstra::new(graph);
-        djikstra.run(&start);
-        let path = djikstra.get_path(&end);
-        assert!(path.len() > 0);
+        djikstra.run(&start).unwrap();
+        let path = djikstra.get_path(&end).unwrap();
+        assert!(!path.is_empty());
         dbg!(path);
     }
     #[test]
@@ -242,9 +469,584 @@ mod tests {
         let end = nodes[5].clone();
         let graph = Graph::new(nodes, edges);
         let mut djikstra = Djikstra::new(graph);
-        djikstra.run(&start);
-        let path = djikstra.get_path(&end);
-        assert!(path.len() > 0);
+        djikstra.run(&start).unwrap();
+        let path = djikstra.get_path(&end).unwrap();
+        assert!(!path.is_empty());
         dbg!(path);
     }
+
+    #[test]
+    fn picks_cheapest_route() {
+        let mut nodes = vec![];
+        let mut edges = vec![];
+
+        nodes.push(Vertex::new("A".into(), "A".into()));
+        nodes.push(Vertex::new("B".into(), "B".into()));
+        nodes.push(Vertex::new("C".into(), "C".into()));
+        nodes.push(Vertex::new("D".into(), "D".into()));
+        nodes.push(Vertex::new("E".into(), "E".into()));
+        nodes.push(Vertex::new("F".into(), "F".into()));
+        add_lane(&nodes, &mut edges, "AB".into(), 0, 1, 10);
+        add_lane(&nodes, &mut edges, "AC".into(), 0, 2, 20);
+        add_lane(&nodes, &mut edges, "BD".into(), 1, 3, 50);
+        add_lane(&nodes, &mut edges, "BE".into(), 1, 4, 10);
+        add_lane(&nodes, &mut edges, "CD".into(), 2, 3, 20);
+        add_lane(&nodes, &mut edges, "CE".into(), 2, 4, 33);
+        add_lane(&nodes, &mut edges, "DE".into(), 3, 4, 20);
+        add_lane(&nodes, &mut edges, "DF".into(), 3, 5, 2);
+        add_lane(&nodes, &mut edges, "EF".into(), 4, 5, 1);
+
+        let start = nodes[0].clone();
+        let unreachable = Vertex::new("X".into(), "X".into());
+        let graph = Graph::new(nodes.clone(), edges);
+        let mut djikstra = Djikstra::new(graph);
+        djikstra.run(&start).unwrap();
+        assert_eq!(
+            djikstra.get_path(&nodes[5]).unwrap(),
+            vec!["A", "B", "E", "F"]
+        );
+        assert_eq!(djikstra.get_path(&nodes[3]).unwrap(), vec!["A", "C", "D"]);
+        assert_eq!(
+            djikstra.get_path(&unreachable),
+            Err(DijkstraError::UnknownTarget("X".into()))
+        );
+    }
+
+    #[test]
+    fn interns_vertex_ids() {
+        let a = Vertex::new("A".into(), "A".into());
+        let b = Vertex::new("B".into(), "B".into());
+        let c = Vertex::new("C".into(), "C".into());
+        let x = Vertex::new("X".into(), "X".into());
+        let edges = vec![
+            Edge::new("AB".into(), a.clone(), b.clone(), 1),
+            Edge::new("BC".into(), b.clone(), c.clone(), 1),
+            Edge::new("BX".into(), b.clone(), x, 1),
+        ];
+        let graph = Graph::new(vec![a.clone(), b.clone(), a.clone(), c.clone()], edges);
+        let mut djikstra = Djikstra::new(graph);
+        assert_eq!(djikstra.vertex_index("A"), Some(VertexIndex(0)));
+        assert_eq!(djikstra.vertex_index("B"), Some(VertexIndex(1)));
+        assert_eq!(djikstra.vertex_index("C"), Some(VertexIndex(2)));
+        assert_eq!(djikstra.vertex_index("X"), None);
+        assert_eq!(djikstra.graph().edge_count(), 2);
+        assert_eq!(djikstra.vertex(VertexIndex(2)), &c);
+
+        djikstra.run(&a).unwrap();
+        assert_eq!(djikstra.get_path(&c).unwrap(), vec!["A", "B", "C"]);
+        assert_eq!(
+            djikstra.run(&Vertex::new("X".into(), "X".into())),
+            Err(DijkstraError::UnknownSource("X".into()))
+        );
+        assert_eq!(
+            djikstra.get_path(&c),
+            Err(DijkstraError::Unreachable("C".into()))
+        );
+    }
+
+    #[test]
+    fn run_to_stops_at_target() {
+        let mut nodes = vec![];
+        let mut edges = vec![];
+
+        nodes.push(Vertex::new("A".into(), "A".into()));
+        nodes.push(Vertex::new("B".into(), "B".into()));
+        nodes.push(Vertex::new("C".into(), "C".into()));
+        nodes.push(Vertex::new("D".into(), "D".into()));
+        add_lane(&nodes, &mut edges, "AB".into(), 0, 1, 10);
+        add_lane(&nodes, &mut edges, "BC".into(), 1, 2, 10);
+        add_lane(&nodes, &mut edges, "AD".into(), 0, 3, 100);
+
+        let graph = Graph::new(nodes.clone(), edges);
+        let mut djikstra = Djikstra::new(graph);
+        let path = djikstra.run_to(&nodes[0], &nodes[1]).unwrap();
+        assert_eq!(path.vertex_ids(), vec!["A", "B"]);
+        assert_eq!(path.cost, 10);
+        assert!(!djikstra.is_settled(VertexIndex(3)));
+
+        let path = djikstra.run_to(&nodes[0], &nodes[0]).unwrap();
+        assert_eq!(path.vertex_ids(), vec!["A"]);
+        assert_eq!(path.cost, 0);
+        assert_eq!(
+            djikstra.run_to(&nodes[2], &nodes[0]),
+            Err(DijkstraError::Unreachable("A".into()))
+        );
+    }
+
+    #[test]
+    fn route_reports_lanes_and_costs() {
+        let mut nodes = vec![];
+        let mut edges = vec![];
+
+        nodes.push(Vertex::new("A".into(), "A".into()));
+        nodes.push(Vertex::new("B".into(), "B".into()));
+        nodes.push(Vertex::new("C".into(), "C".into()));
+        nodes.push(Vertex::new("D".into(), "D".into()));
+        nodes.push(Vertex::new("E".into(), "E".into()));
+        add_lane(&nodes, &mut edges, "AB".into(), 0, 1, 10);
+        add_lane(&nodes, &mut edges, "AD".into(), 0, 3, 80);
+        add_lane(&nodes, &mut edges, "BE".into(), 1, 4, 20);
+        add_lane(&nodes, &mut edges, "BC".into(), 1, 2, 50);
+        add_lane(&nodes, &mut edges, "DC".into(), 3, 2, 50);
+        add_lane(&nodes, &mut edges, "CE".into(), 2, 4, 50);
+        add_lane(&nodes, &mut edges, "EC".into(), 4, 2, 20);
+        add_lane(&nodes, &mut edges, "ED".into(), 4, 3, 40);
+
+        let graph = Graph::new(nodes.clone(), edges);
+        let mut djikstra = Djikstra::new(graph);
+        djikstra.run(&nodes[0]).unwrap();
+        let path = djikstra.get_route(&nodes[2]).unwrap();
+        assert_eq!(path.vertex_ids(), vec!["A", "B", "E", "C"]);
+        assert_eq!(path.edges, vec!["AB", "BE", "EC"]);
+        assert_eq!(path.weights, vec![10, 20, 20]);
+        assert_eq!(path.cost, 50);
+        assert_eq!(path.source(), &nodes[0]);
+        assert_eq!(path.target(), &nodes[2]);
+    }
+
+    #[test]
+    fn parallel_lanes() {
+        let mut nodes = vec![];
+        let mut edges = vec![];
+
+        nodes.push(Vertex::new("A".into(), "A".into()));
+        nodes.push(Vertex::new("B".into(), "B".into()));
+        nodes.push(Vertex::new("C".into(), "C".into()));
+        add_lane(&nodes, &mut edges, "AB-road".into(), 0, 1, 10);
+        add_lane(&nodes, &mut edges, "AB-rail".into(), 0, 1, 4);
+        add_lane(&nodes, &mut edges, "AB-air".into(), 0, 1, 7);
+        add_lane(&nodes, &mut edges, "BC-rail".into(), 1, 2, 3);
+        add_lane(&nodes, &mut edges, "BC-road".into(), 1, 2, 9);
+
+        let graph = Graph::new(nodes.clone(), edges);
+        let mut djikstra = Djikstra::new(graph);
+        let path = djikstra.run_to(&nodes[0], &nodes[2]).unwrap();
+        assert_eq!(path.edges, vec!["AB-rail", "BC-rail"]);
+        assert_eq!(path.weights, vec![4, 3]);
+        assert_eq!(path.cost, 7);
+    }
+
+    #[test]
+    fn errors() {
+        let mut nodes = vec![];
+        let mut edges = vec![];
+
+        nodes.push(Vertex::new("A".into(), "A".into()));
+        nodes.push(Vertex::new("B".into(), "B".into()));
+        nodes.push(Vertex::new("C".into(), "C".into()));
+        add_lane(&nodes, &mut edges, "AB".into(), 0, 1, i32::MAX - 1);
+        add_lane(&nodes, &mut edges, "BC".into(), 1, 2, 2);
+
+        let graph = Graph::new(nodes.clone(), edges.clone());
+        let mut djikstra = Djikstra::new(graph);
+        assert_eq!(djikstra.run(&nodes[0]), Err(DijkstraError::Overflow));
+        assert_eq!(
+            djikstra.run_to(&nodes[1], &Vertex::new("X".into(), "X".into())),
+            Err(DijkstraError::UnknownTarget("X".into()))
+        );
+        assert_eq!(djikstra.run_to(&nodes[1], &nodes[1]).unwrap().cost, 0);
+        let negative = djikstra.run_from_sources(vec![(&nodes[0], 0), (&nodes[2], -3)]);
+        assert_eq!(negative, Err(DijkstraError::NegativeOffset("C".into())));
+        assert_eq!(
+            negative.unwrap_err().to_string(),
+            "source `C` has a negative offset"
+        );
+
+        add_lane(&nodes, &mut edges, "CA".into(), 2, 0, -1);
+        let graph = Graph::new(nodes.clone(), edges);
+        let mut djikstra = Djikstra::new(graph);
+        assert_eq!(
+            djikstra.run(&nodes[1]),
+            Err(DijkstraError::NegativeWeight("CA".into()))
+        );
+    }
+
+    #[test]
+    fn generic_weights() {
+        use std::time::Duration;
+
+        let a = Vertex::new("A".into(), "A".into());
+        let b = Vertex::new("B".into(), "B".into());
+        let c = Vertex::new("C".into(), "C".into());
+        let week = Duration::from_secs(7 * 24 * 60 * 60);
+        let edges = vec![
+            Edge::new("AB".into(), a.clone(), b.clone(), week * 2),
+            Edge::new("BC".into(), b.clone(), c.clone(), week * 2),
+            Edge::new("AC".into(), a.clone(), c.clone(), week * 5),
+        ];
+        let graph = Graph::new(vec![a.clone(), b.clone(), c.clone()], edges);
+        let mut djikstra = Djikstra::new(graph);
+        let path = djikstra.run_to(&a, &c).unwrap();
+        assert_eq!(path.edges, vec!["AB", "BC"]);
+        assert_eq!(path.cost, week * 4);
+
+        let edges = vec![
+            Edge::new("AB".into(), a.clone(), b.clone(), TotalF64(0.25)),
+            Edge::new("BC".into(), b.clone(), c.clone(), TotalF64(0.5)),
+            Edge::new("AC".into(), a.clone(), c.clone(), TotalF64(1.0)),
+        ];
+        let graph = Graph::new(vec![a.clone(), b, c.clone()], edges);
+        let mut djikstra = Djikstra::new(graph);
+        assert_eq!(djikstra.run_to(&a, &c).unwrap().cost, TotalF64(0.75));
+
+        let edges = vec![Edge::new("AC".into(), a.clone(), c.clone(), u64::MAX - 1)];
+        let graph = Graph::new(vec![a.clone(), c.clone()], edges);
+        let mut djikstra = Djikstra::new(graph);
+        assert_eq!(djikstra.run_to(&a, &c).unwrap().cost, u64::MAX - 1);
+    }
+
+    #[test]
+    fn custom_payloads() {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Depot {
+            code: String,
+            latitude: f64,
+        }
+
+        impl GraphVertex for Depot {
+            fn id(&self) -> &str {
+                &self.code
+            }
+        }
+
+        #[derive(Debug, Clone, PartialEq)]
+        struct Lane {
+            code: String,
+            from: String,
+            to: String,
+            carrier: &'static str,
+            minutes: u32,
+        }
+
+        impl GraphEdge for Lane {
+            type Weight = u32;
+
+            fn id(&self) -> &str {
+                &self.code
+            }
+
+            fn source(&self) -> &str {
+                &self.from
+            }
+
+            fn destination(&self) -> &str {
+                &self.to
+            }
+
+            fn weight(&self) -> u32 {
+                self.minutes
+            }
+        }
+
+        let depot = |code: &str, latitude| Depot {
+            code: code.into(),
+            latitude,
+        };
+        let lane = |code: &str, carrier, minutes| Lane {
+            code: code.into(),
+            from: code[..3].into(),
+            to: code[3..].into(),
+            carrier,
+            minutes,
+        };
+        let graph = Graph::new(
+            vec![depot("HAM", 53.5), depot("BRE", 53.1), depot("MUC", 48.1)],
+            vec![
+                lane("HAMBRE", "north", 70),
+                lane("BREMUC", "south", 480),
+                lane("HAMMUC", "express", 600),
+            ],
+        );
+        assert!(graph.validate().is_ok());
+        let mut djikstra = Djikstra::new(graph);
+        let path = djikstra.run_to("HAM", "MUC").unwrap();
+        assert_eq!(path.cost, 550);
+        assert_eq!(path.source().latitude, 53.5);
+        let carriers: Vec<_> = path.lanes.iter().map(|lane| lane.carrier).collect();
+        assert_eq!(carriers, vec!["north", "south"]);
+    }
+
+    #[test]
+    fn tie_breaking() {
+        let mut nodes = vec![];
+        let mut edges = vec![];
+
+        for id in ["A", "B", "C", "D", "P", "Q", "Y"].iter() {
+            nodes.push(Vertex::new(id.to_string(), id.to_string()));
+        }
+        add_lane(&nodes, &mut edges, "QD".into(), 5, 3, 8);
+        add_lane(&nodes, &mut edges, "AP".into(), 0, 4, 1);
+        add_lane(&nodes, &mut edges, "PQ".into(), 4, 5, 1);
+        add_lane(&nodes, &mut edges, "AB".into(), 0, 1, 2);
+        add_lane(&nodes, &mut edges, "BC".into(), 1, 2, 2);
+        add_lane(&nodes, &mut edges, "CD".into(), 2, 3, 6);
+        add_lane(&nodes, &mut edges, "AY".into(), 0, 6, 5);
+        add_lane(&nodes, &mut edges, "YD".into(), 6, 3, 5);
+
+        let expected = [
+            (TieBreak::InsertionOrder, vec!["A", "P", "Q", "D"]),
+            (TieBreak::FewestHops, vec!["A", "Y", "D"]),
+            (TieBreak::LowestVertexId, vec!["A", "B", "C", "D"]),
+        ];
+        for (tie_break, path) in expected.iter() {
+            let graph = Graph::new(nodes.clone(), edges.clone());
+            let mut djikstra = Djikstra::new(graph).with_tie_break(*tie_break);
+            for _ in 0..3 {
+                let route = djikstra.run_to(&nodes[0], &nodes[3]).unwrap();
+                assert_eq!(&route.vertex_ids(), path);
+                assert_eq!(route.cost, 10);
+            }
+        }
+
+        let nodes: Vec<_> = ["A", "T", "B"]
+            .iter()
+            .map(|id| Vertex::new(id.to_string(), id.to_string()))
+            .collect();
+        let mut edges = vec![];
+        add_lane(&nodes, &mut edges, "AB".into(), 0, 2, 1);
+        add_lane(&nodes, &mut edges, "BT".into(), 2, 1, 0);
+        add_lane(&nodes, &mut edges, "AT".into(), 0, 1, 1);
+        let expected = [
+            (TieBreak::InsertionOrder, vec!["AB", "BT"]),
+            (TieBreak::FewestHops, vec!["AT"]),
+            (TieBreak::LowestVertexId, vec!["AT"]),
+        ];
+        for (tie_break, lanes) in expected.iter() {
+            let graph = Graph::new(nodes.clone(), edges.clone());
+            let mut djikstra = Djikstra::new(graph).with_tie_break(*tie_break);
+            assert_eq!(&djikstra.run_to("A", "T").unwrap().edges, lanes);
+            djikstra.run("A").unwrap();
+            assert_eq!(&djikstra.get_route("T").unwrap().edges, lanes);
+        }
+    }
+
+    #[test]
+    fn concurrent_queries() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<CsrGraph>();
+        assert_send_sync::<Djikstra>();
+
+        let mut nodes = vec![];
+        let mut edges = vec![];
+        for i in 0..50 {
+            nodes.push(Vertex::new(i.to_string(), i.to_string()));
+        }
+        for i in 0..49 {
+            add_lane(&nodes, &mut edges, format!("{}+", i), i, i + 1, 1);
+            add_lane(&nodes, &mut edges, format!("{}-", i), i + 1, i, 2);
+        }
+        let network = Arc::new(CsrGraph::from(Graph::new(nodes.clone(), edges)));
+
+        let costs: Vec<i32> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|t| {
+                    let network = Arc::clone(&network);
+                    let nodes = &nodes;
+                    scope.spawn(move || {
+                        let mut djikstra = Djikstra::from_shared(network);
+                        djikstra
+                            .run_to(&nodes[t * 10], &nodes[49 - t])
+                            .unwrap()
+                            .cost
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        assert_eq!(costs, vec![49, 38, 27, 16]);
+        assert_eq!(Arc::strong_count(&network), 1);
+    }
+
+    #[test]
+    fn reused_workspace() {
+        let mut nodes = vec![];
+        let mut edges = vec![];
+
+        for id in ["A", "B", "C", "D"].iter() {
+            nodes.push(Vertex::new(id.to_string(), id.to_string()));
+        }
+        add_lane(&nodes, &mut edges, "AB".into(), 0, 1, 1);
+        add_lane(&nodes, &mut edges, "BC".into(), 1, 2, 1);
+        add_lane(&nodes, &mut edges, "CD".into(), 2, 3, 1);
+        add_lane(&nodes, &mut edges, "DB".into(), 3, 1, 1);
+
+        let mut reused = Djikstra::new(Graph::new(nodes.clone(), edges.clone()));
+        for _ in 0..2 {
+            for source in &nodes {
+                let mut fresh = Djikstra::new(Graph::new(nodes.clone(), edges.clone()));
+                reused.run(source).unwrap();
+                fresh.run(source).unwrap();
+                for target in &nodes {
+                    assert_eq!(reused.get_route(target), fresh.get_route(target));
+                }
+            }
+        }
+        reused.run(&nodes[2]).unwrap();
+        assert_eq!(
+            reused.get_path(&nodes[0]),
+            Err(DijkstraError::Unreachable("A".into()))
+        );
+    }
+
+    #[test]
+    fn one_to_all_table() {
+        let mut nodes = vec![];
+        let mut edges = vec![];
+
+        for id in ["A", "B", "C", "D", "E"].iter() {
+            nodes.push(Vertex::new(id.to_string(), id.to_string()));
+        }
+        add_lane(&nodes, &mut edges, "AB".into(), 0, 1, 10);
+        add_lane(&nodes, &mut edges, "AC".into(), 0, 2, 3);
+        add_lane(&nodes, &mut edges, "CB".into(), 2, 1, 4);
+        add_lane(&nodes, &mut edges, "BD".into(), 1, 3, 2);
+        add_lane(&nodes, &mut edges, "EA".into(), 4, 0, 1);
+
+        let mut djikstra = Djikstra::new(Graph::new(nodes.clone(), edges));
+        djikstra.run(&nodes[0]).unwrap();
+        assert_eq!(djikstra.distance_to(&nodes[3]), Ok(9));
+        assert_eq!(
+            djikstra.distance_to(&nodes[4]),
+            Err(DijkstraError::Unreachable("E".into()))
+        );
+
+        let table: Vec<_> = djikstra
+            .reached()
+            .map(|(vertex, cost)| (vertex.id.as_str(), cost))
+            .collect();
+        assert_eq!(table, vec![("A", 0), ("C", 3), ("B", 7), ("D", 9)]);
+
+        let tree: Vec<_> = djikstra
+            .predecessor_tree()
+            .map(|(vertex, parent, lane)| {
+                (vertex.id.as_str(), parent.id.as_str(), lane.id.as_str())
+            })
+            .collect();
+        assert_eq!(
+            tree,
+            vec![("C", "A", "AC"), ("B", "C", "CB"), ("D", "B", "BD")]
+        );
+
+        assert_eq!(djikstra.predecessor_of(&nodes[0]), Ok(None));
+        let (parent, lane) = djikstra.predecessor_of(&nodes[1]).unwrap().unwrap();
+        assert_eq!((parent.id.as_str(), lane.id.as_str()), ("C", "CB"));
+
+        djikstra.run_to(&nodes[0], &nodes[2]).unwrap();
+        assert_eq!(
+            djikstra.distance_to(&nodes[1]),
+            Err(DijkstraError::Unreachable("B".into()))
+        );
+    }
+
+    #[test]
+    fn nearest_depot() {
+        let mut nodes = vec![];
+        let mut edges = vec![];
+
+        for id in ["D1", "D2", "D3", "X", "Y", "Z"].iter() {
+            nodes.push(Vertex::new(id.to_string(), id.to_string()));
+        }
+        add_lane(&nodes, &mut edges, "D1X".into(), 0, 3, 10);
+        add_lane(&nodes, &mut edges, "D2X".into(), 1, 3, 4);
+        add_lane(&nodes, &mut edges, "XY".into(), 3, 4, 5);
+        add_lane(&nodes, &mut edges, "D3Y".into(), 2, 4, 11);
+        add_lane(&nodes, &mut edges, "D1Z".into(), 0, 5, 1);
+
+        let mut djikstra = Djikstra::new(Graph::new(nodes.clone(), edges));
+        djikstra
+            .run_from_sources(vec![(&nodes[0], 0), (&nodes[1], 3), (&nodes[2], 0)])
+            .unwrap();
+
+        let origins: Vec<_> = djikstra
+            .reached()
+            .map(|(vertex, cost)| {
+                let origin = djikstra.origin_of(vertex).unwrap();
+                (vertex.id.as_str(), origin.id.as_str(), cost)
+            })
+            .collect();
+        assert_eq!(
+            origins,
+            vec![
+                ("D1", "D1", 0),
+                ("D3", "D3", 0),
+                ("Z", "D1", 1),
+                ("D2", "D2", 3),
+                ("X", "D2", 7),
+                ("Y", "D3", 11),
+            ]
+        );
+    }
+
+    #[test]
+    fn two_way_lanes() {
+        let a = Vertex::new("A".into(), "A".into());
+        let b = Vertex::new("B".into(), "B".into());
+        let c = Vertex::new("C".into(), "C".into());
+        let edges = vec![
+            Edge::new("AB".into(), a.clone(), b.clone(), 2).with_direction(Direction::Symmetric),
+            Edge::new("BC".into(), b.clone(), c.clone(), 3)
+                .with_direction(Direction::Asymmetric { backward: 7 }),
+            Edge::new("CA".into(), c.clone(), a.clone(), 20),
+        ];
+        let graph = Graph::new(vec![a, b, c], edges.clone());
+
+        let csr = CsrGraph::from(graph.clone());
+        assert_eq!(csr.edge_count(), 5);
+        assert_eq!(csr.lane_count(), 3);
+        assert_eq!(csr.to_graph().edges, edges);
+
+        let mut djikstra = Djikstra::new(graph);
+        assert_eq!(djikstra.run_to("A", "C").unwrap().cost, 5);
+
+        let route = djikstra.run_to("C", "A").unwrap();
+        assert_eq!(route.vertex_ids(), vec!["C", "B", "A"]);
+        assert_eq!(route.edges, vec!["BC", "AB"]);
+        assert_eq!(route.weights, vec![7, 2]);
+        assert_eq!(route.cost, 9);
+    }
+
+    #[test]
+    fn isochrones() {
+        let nodes: Vec<_> = ["HUB", "A", "B", "C", "D", "E"]
+            .iter()
+            .map(|id| Vertex::new(id.to_string(), id.to_string()))
+            .collect();
+        let mut edges = vec![];
+        add_lane(&nodes, &mut edges, "HA".into(), 0, 1, 45);
+        add_lane(&nodes, &mut edges, "HB".into(), 0, 2, 60);
+        add_lane(&nodes, &mut edges, "AC".into(), 1, 3, 70);
+        add_lane(&nodes, &mut edges, "CD".into(), 3, 4, 100);
+        add_lane(&nodes, &mut edges, "DE".into(), 4, 5, 300);
+
+        let mut djikstra = Djikstra::new(Graph::new(nodes, edges));
+        djikstra.run_within("HUB", 120).unwrap();
+        let within: Vec<_> = djikstra
+            .reached()
+            .map(|(vertex, cost)| (vertex.id.as_str(), cost))
+            .collect();
+        assert_eq!(within, vec![("HUB", 0), ("A", 45), ("B", 60), ("C", 115)]);
+        assert_eq!(
+            djikstra.distance_to("D"),
+            Err(DijkstraError::Unreachable("D".into()))
+        );
+
+        let bands: Vec<_> = djikstra
+            .isochrones("HUB", &[240, 60, 120])
+            .unwrap()
+            .into_iter()
+            .map(|(band, vertices)| {
+                let ids: Vec<_> = vertices.iter().map(|v| v.id.as_str()).collect();
+                (band, ids)
+            })
+            .collect();
+        assert_eq!(
+            bands,
+            vec![
+                (60, vec!["HUB", "A", "B"]),
+                (120, vec!["C"]),
+                (240, vec!["D"]),
+            ]
+        );
+        assert!(djikstra.isochrones("HUB", &[]).unwrap().is_empty());
+    }
 }
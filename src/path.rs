@@ -0,0 +1,88 @@
+use crate::{CsrGraph, Edge, GraphEdge, GraphVertex, Vertex, VertexIndex};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<V = Vertex, E = Edge>
+where
+    E: GraphEdge,
+{
+    pub vertices: Vec<V>,
+    pub edges: Vec<String>,
+    pub lanes: Vec<E>,
+    pub weights: Vec<E::Weight>,
+    pub cost: E::Weight,
+}
+
+impl<V: GraphVertex, E: GraphEdge> Path<V, E> {
+    pub fn source(&self) -> &V {
+        &self.vertices[0]
+    }
+
+    pub fn target(&self) -> &V {
+        &self.vertices[self.vertices.len() - 1]
+    }
+
+    pub fn hops(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn vertex_ids(&self) -> Vec<String> {
+        self.vertices.iter().map(|v| v.id().to_string()).collect()
+    }
+}
+
+impl<V: GraphVertex + Clone, E: GraphEdge + Clone> Path<V, E> {
+    pub(crate) fn from_predecessors(
+        graph: &CsrGraph<V, E>,
+        predecessor: impl Fn(VertexIndex) -> Option<(VertexIndex, usize)>,
+        target: VertexIndex,
+        cost: E::Weight,
+    ) -> Self {
+        Self::through(graph, predecessor, |_| None, target, cost)
+    }
+
+    pub(crate) fn through(
+        graph: &CsrGraph<V, E>,
+        predecessor: impl Fn(VertexIndex) -> Option<(VertexIndex, usize)>,
+        successor: impl Fn(VertexIndex) -> Option<(VertexIndex, usize)>,
+        meeting: VertexIndex,
+        cost: E::Weight,
+    ) -> Self {
+        let mut arcs = vec![];
+        let mut start = meeting;
+        while let Some((previous, edge)) = predecessor(start) {
+            arcs.push(edge);
+            start = previous;
+        }
+        arcs.reverse();
+
+        let mut step = meeting;
+        while let Some((next, edge)) = successor(step) {
+            arcs.push(edge);
+            step = next;
+        }
+        Self::from_arcs(graph, start, &arcs, cost)
+    }
+
+    pub(crate) fn from_arcs(
+        graph: &CsrGraph<V, E>,
+        source: VertexIndex,
+        arcs: &[usize],
+        cost: E::Weight,
+    ) -> Self {
+        let mut vertices = vec![graph.vertex(source).clone()];
+        vertices.extend(
+            arcs.iter()
+                .map(|&edge| graph.vertex(graph.edge_target(edge)).clone()),
+        );
+        Path {
+            vertices,
+            edges: arcs
+                .iter()
+                .map(|&edge| graph.edge_id(edge).to_string())
+                .collect(),
+            lanes: arcs.iter().map(|&edge| graph.edge(edge).clone()).collect(),
+            weights: arcs.iter().map(|&edge| graph.edge_weight(edge)).collect(),
+            cost,
+        }
+    }
+}
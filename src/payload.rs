@@ -0,0 +1,71 @@
+use crate::{Direction, Edge, Vertex, Weight};
+
+pub trait GraphVertex {
+    fn id(&self) -> &str;
+
+    fn name(&self) -> &str {
+        self.id()
+    }
+}
+
+pub trait GraphEdge {
+    type Weight: Weight;
+
+    fn id(&self) -> &str;
+
+    fn source(&self) -> &str;
+
+    fn destination(&self) -> &str;
+
+    fn weight(&self) -> Self::Weight;
+
+    fn direction(&self) -> Direction<Self::Weight> {
+        Direction::Directed
+    }
+}
+
+impl GraphVertex for Vertex {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl GraphVertex for str {
+    fn id(&self) -> &str {
+        self
+    }
+}
+
+impl GraphVertex for String {
+    fn id(&self) -> &str {
+        self
+    }
+}
+
+impl<W: Weight> GraphEdge for Edge<W> {
+    type Weight = W;
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn source(&self) -> &str {
+        &self.source.id
+    }
+
+    fn destination(&self) -> &str {
+        &self.destination.id
+    }
+
+    fn weight(&self) -> W {
+        self.weight
+    }
+
+    fn direction(&self) -> Direction<W> {
+        self.direction
+    }
+}
@@ -0,0 +1,215 @@
+use crate::workspace::State;
+use crate::{
+    DijkstraError, Direction, Djikstra, Graph, GraphEdge, GraphVertex, Path, VertexIndex, Weight,
+};
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct Reversed<E>(pub E);
+
+impl<E: GraphEdge> GraphEdge for Reversed<E> {
+    type Weight = E::Weight;
+
+    fn id(&self) -> &str {
+        self.0.id()
+    }
+
+    fn source(&self) -> &str {
+        self.0.destination()
+    }
+
+    fn destination(&self) -> &str {
+        self.0.source()
+    }
+
+    fn weight(&self) -> E::Weight {
+        self.0.weight()
+    }
+
+    fn direction(&self) -> Direction<E::Weight> {
+        self.0.direction()
+    }
+}
+
+impl<V, E> Graph<V, E> {
+    pub fn reversed(self) -> Graph<V, Reversed<E>> {
+        Graph::new(
+            self.vertices,
+            self.edges.into_iter().map(Reversed).collect(),
+        )
+    }
+}
+
+impl<V: GraphVertex + Clone, E: GraphEdge + Clone> Djikstra<V, E> {
+    pub fn run_to_target<T: GraphVertex + ?Sized>(
+        &mut self,
+        target: &T,
+    ) -> Result<(), DijkstraError> {
+        self.reverse.reset(self.graph.vertex_count());
+
+        if let Some(edge) = self.graph.negative_edge() {
+            return Err(DijkstraError::NegativeWeight(
+                self.graph.edge_id(edge).to_string(),
+            ));
+        }
+        let target = self
+            .vertex_index(target.id())
+            .ok_or_else(|| DijkstraError::UnknownTarget(target.id().to_string()))?;
+        self.reverse.start(target, E::Weight::zero());
+
+        while let Some(State { vertex: node, .. }) = self.reverse.pop_unsettled() {
+            self.reverse.settle(node);
+            for &edge in self.graph.incoming(node) {
+                let source = self.graph.edge_source(edge);
+                let cost = self
+                    .reverse
+                    .distance(node)
+                    .checked_add(self.graph.edge_weight(edge));
+                if self.reverse.is_settled(source) {
+                    if cost == Some(self.reverse.distance(source)) {
+                        self.tie_break.tie_reverse(
+                            &self.graph,
+                            &mut self.reverse,
+                            node,
+                            edge,
+                            source,
+                        );
+                    }
+                    continue;
+                }
+                let cost = cost.ok_or(DijkstraError::Overflow)?;
+                let current = self.reverse.distance(source);
+                if cost < current {
+                    self.reverse.improve(source, cost, node, edge);
+                } else if cost == current {
+                    self.tie_break
+                        .tie_reverse(&self.graph, &mut self.reverse, node, edge, source);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn distance_from<S: GraphVertex + ?Sized>(
+        &self,
+        source: &S,
+    ) -> Result<E::Weight, DijkstraError> {
+        let index = self.reaching_index(source)?;
+        Ok(self.reverse.distance(index))
+    }
+
+    pub fn next_hop_of<S: GraphVertex + ?Sized>(
+        &self,
+        source: &S,
+    ) -> Result<Option<(&V, &E)>, DijkstraError> {
+        let index = self.reaching_index(source)?;
+        Ok(self
+            .reverse
+            .predecessor(index)
+            .map(|(next, edge)| (self.vertex(next), self.graph.edge(edge))))
+    }
+
+    pub fn route_from<S: GraphVertex + ?Sized>(
+        &self,
+        source: &S,
+    ) -> Result<Path<V, E>, DijkstraError> {
+        let index = self.reaching_index(source)?;
+        Ok(Path::through(
+            &self.graph,
+            |_| None,
+            |vertex| self.reverse.predecessor(vertex),
+            index,
+            self.reverse.distance(index),
+        ))
+    }
+
+    pub fn reaching(&self) -> impl Iterator<Item = (&V, E::Weight)> + '_ {
+        self.reverse
+            .settled()
+            .iter()
+            .map(move |&vertex| (self.vertex(vertex), self.reverse.distance(vertex)))
+    }
+
+    fn reaching_index<S: GraphVertex + ?Sized>(
+        &self,
+        source: &S,
+    ) -> Result<VertexIndex, DijkstraError> {
+        let index = self
+            .vertex_index(source.id())
+            .ok_or_else(|| DijkstraError::UnknownSource(source.id().to_string()))?;
+        if !self.reverse.is_settled(index) {
+            return Err(DijkstraError::Unreachable(source.id().to_string()));
+        }
+        Ok(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{add_lane, vertices};
+    use crate::TieBreak;
+
+    #[test]
+    fn all_to_hub() {
+        let nodes = vertices(&["A", "B", "C", "D", "HUB"]);
+        let mut edges = vec![];
+        add_lane(&nodes, &mut edges, "AB".into(), 0, 1, 2);
+        add_lane(&nodes, &mut edges, "AH".into(), 0, 4, 9);
+        add_lane(&nodes, &mut edges, "BH".into(), 1, 4, 5);
+        add_lane(&nodes, &mut edges, "CB".into(), 2, 1, 1);
+        add_lane(&nodes, &mut edges, "HD".into(), 4, 3, 1);
+        let graph = Graph::new(nodes.clone(), edges);
+
+        let mut djikstra = Djikstra::new(graph.clone());
+        djikstra.run_to_target("HUB").unwrap();
+        let costs: Vec<_> = djikstra
+            .reaching()
+            .map(|(vertex, cost)| (vertex.id.as_str(), cost))
+            .collect();
+        assert_eq!(costs, vec![("HUB", 0), ("B", 5), ("C", 6), ("A", 7)]);
+
+        let (next, lane) = djikstra.next_hop_of("A").unwrap().unwrap();
+        assert_eq!((next.id.as_str(), lane.id.as_str()), ("B", "AB"));
+        assert!(djikstra.next_hop_of("HUB").unwrap().is_none());
+        assert_eq!(
+            djikstra.route_from("C").unwrap().vertex_ids(),
+            vec!["C", "B", "HUB"]
+        );
+        assert_eq!(
+            djikstra.distance_from("D"),
+            Err(DijkstraError::Unreachable("D".into()))
+        );
+
+        let mut transposed = Djikstra::new(graph.reversed());
+        transposed.run("HUB").unwrap();
+        for (vertex, cost) in djikstra.reaching() {
+            assert_eq!(transposed.distance_to(vertex), Ok(cost));
+        }
+        assert_eq!(transposed.get_path("A").unwrap(), vec!["HUB", "B", "A"]);
+    }
+
+    #[test]
+    fn reverse_table_survives_and_breaks_ties() {
+        let nodes = vertices(&["A", "B", "C", "D", "T"]);
+        let mut edges = vec![];
+        add_lane(&nodes, &mut edges, "AB".into(), 0, 1, 1);
+        add_lane(&nodes, &mut edges, "BC".into(), 1, 2, 1);
+        add_lane(&nodes, &mut edges, "CT".into(), 2, 4, 1);
+        add_lane(&nodes, &mut edges, "AD".into(), 0, 3, 2);
+        add_lane(&nodes, &mut edges, "DT".into(), 3, 4, 1);
+        let expected = [
+            (TieBreak::InsertionOrder, vec!["AB", "BC", "CT"]),
+            (TieBreak::FewestHops, vec!["AD", "DT"]),
+            (TieBreak::LowestVertexId, vec!["AB", "BC", "CT"]),
+        ];
+        for (tie_break, lanes) in expected.iter() {
+            let graph = Graph::new(nodes.clone(), edges.clone());
+            let mut djikstra = Djikstra::new(graph).with_tie_break(*tie_break);
+            djikstra.run_to_target("T").unwrap();
+            djikstra.run_bidirectional("B", "C").unwrap();
+            assert_eq!(djikstra.distance_from("A"), Ok(3));
+            assert_eq!(&djikstra.route_from("A").unwrap().edges, lanes);
+            assert_eq!(&djikstra.run_to("A", "T").unwrap().edges, lanes);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use crate::geo::{chord_to_km, Coordinates, Located};
+use crate::{CsrGraph, GraphEdge, GraphVertex, VertexIndex};
+
+#[derive(Debug, Clone)]
+pub struct SpatialIndex {
+    points: Vec<([f64; 3], VertexIndex)>,
+}
+
+impl SpatialIndex {
+    pub fn new<V, E>(graph: &CsrGraph<V, E>) -> Self
+    where
+        V: GraphVertex + Located,
+        E: GraphEdge,
+    {
+        Self::from_points(graph.vertices().iter().enumerate().filter_map(|(i, v)| {
+            v.coordinates()
+                .map(|coordinates| (VertexIndex(i as u32), coordinates))
+        }))
+    }
+
+    pub fn from_points(points: impl IntoIterator<Item = (VertexIndex, Coordinates)>) -> Self {
+        let mut points: Vec<_> = points
+            .into_iter()
+            .map(|(vertex, coordinates)| (coordinates.to_unit_vector(), vertex))
+            .collect();
+        build(&mut points, 0);
+        SpatialIndex { points }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn nearest(&self, coordinates: Coordinates) -> Option<(VertexIndex, f64)> {
+        let query = coordinates.to_unit_vector();
+        let mut best = None;
+        search(&self.points, 0, &query, &mut best);
+        best.map(|(squared, vertex)| (vertex, chord_to_km(f64::sqrt(squared))))
+    }
+}
+
+fn build(points: &mut [([f64; 3], VertexIndex)], depth: usize) {
+    if points.len() <= 1 {
+        return;
+    }
+    let axis = depth % 3;
+    let middle = points.len() / 2;
+    points.select_nth_unstable_by(middle, |a, b| {
+        a.0[axis].total_cmp(&b.0[axis]).then_with(|| a.1.cmp(&b.1))
+    });
+    let (left, right) = points.split_at_mut(middle);
+    build(left, depth + 1);
+    build(&mut right[1..], depth + 1);
+}
+
+fn search(
+    points: &[([f64; 3], VertexIndex)],
+    depth: usize,
+    query: &[f64; 3],
+    best: &mut Option<(f64, VertexIndex)>,
+) {
+    if points.is_empty() {
+        return;
+    }
+    let middle = points.len() / 2;
+    let (point, vertex) = points[middle];
+    let squared: f64 = (0..3).map(|i| (point[i] - query[i]).powi(2)).sum();
+    match *best {
+        Some((distance, index)) if (squared, vertex) >= (distance, index) => {}
+        _ => *best = Some((squared, vertex)),
+    }
+
+    let axis = depth % 3;
+    let offset = query[axis] - point[axis];
+    let (near, far) = if offset < 0.0 {
+        (&points[..middle], &points[middle + 1..])
+    } else {
+        (&points[middle + 1..], &points[..middle])
+    };
+    search(near, depth + 1, query, best);
+    match *best {
+        Some((distance, _)) if offset * offset > distance => {}
+        _ => search(far, depth + 1, query, best),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Graph, Vertex};
+
+    #[test]
+    fn nearest_matches_linear_scan() {
+        let mut vertices = vec![Vertex::new("unplaced".into(), "unplaced".into())];
+        for i in 0..200 {
+            let latitude = ((i * 37) % 170) as f64 - 85.0 + (i as f64) / 1000.0;
+            let longitude = ((i * 91) % 359) as f64 - 179.5;
+            vertices.push(
+                Vertex::new(i.to_string(), i.to_string()).with_coordinates(latitude, longitude),
+            );
+        }
+        let graph: CsrGraph = CsrGraph::from(Graph::new(vertices.clone(), vec![]));
+        let index = SpatialIndex::new(&graph);
+        assert_eq!(index.len(), 200);
+
+        for q in 0..50 {
+            let query = Coordinates::new(
+                ((q * 53) % 180) as f64 - 90.0,
+                ((q * 29) % 360) as f64 - 180.0,
+            );
+            let (vertex, km) = index.nearest(query).unwrap();
+            let expected = vertices
+                .iter()
+                .filter_map(|v| v.coordinates.map(|c| c.distance_km(&query)))
+                .fold(f64::INFINITY, f64::min);
+            assert!((km - expected).abs() < 1e-6);
+            let found = graph.vertex(vertex).coordinates.unwrap();
+            assert!((found.distance_km(&query) - expected).abs() < 1e-6);
+        }
+
+        assert_eq!(
+            SpatialIndex::from_points(vec![]).nearest(Coordinates::new(0.0, 0.0)),
+            None
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use std::cmp::Ordering;
+
+use crate::workspace::Workspace;
+use crate::{CsrGraph, GraphEdge, GraphVertex, VertexIndex};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TieBreak {
+    #[default]
+    InsertionOrder,
+    FewestHops,
+    LowestVertexId,
+}
+
+impl TieBreak {
+    pub(crate) fn tie<V: GraphVertex, E: GraphEdge>(
+        self,
+        graph: &CsrGraph<V, E>,
+        workspace: &mut Workspace<E::Weight>,
+        node: VertexIndex,
+        edge: usize,
+        target: VertexIndex,
+    ) {
+        self.resolve(graph, workspace, node, edge, target, false);
+    }
+
+    pub(crate) fn tie_reverse<V: GraphVertex, E: GraphEdge>(
+        self,
+        graph: &CsrGraph<V, E>,
+        workspace: &mut Workspace<E::Weight>,
+        node: VertexIndex,
+        edge: usize,
+        target: VertexIndex,
+    ) {
+        self.resolve(graph, workspace, node, edge, target, true);
+    }
+
+    fn resolve<V: GraphVertex, E: GraphEdge>(
+        self,
+        graph: &CsrGraph<V, E>,
+        workspace: &mut Workspace<E::Weight>,
+        node: VertexIndex,
+        edge: usize,
+        target: VertexIndex,
+        reverse: bool,
+    ) {
+        let settled = workspace.is_settled(target);
+        if settled && workspace.is_tied_ancestor(target, node) {
+            return;
+        }
+        workspace.add_optimal_predecessor(target, node, edge);
+        if self.prefers(graph, workspace, node, edge, target) {
+            workspace.set_predecessor(target, node, edge);
+            if settled {
+                self.reconsider_successors(graph, workspace, target, reverse);
+            }
+        }
+    }
+
+    // Hops and origins derive from the predecessor, so a vertex that changes
+    // its predecessor after settling hands the change on to its successors.
+    fn reconsider_successors<V: GraphVertex, E: GraphEdge>(
+        self,
+        graph: &CsrGraph<V, E>,
+        workspace: &mut Workspace<E::Weight>,
+        vertex: VertexIndex,
+        reverse: bool,
+    ) {
+        let mut stack = vec![vertex];
+        while let Some(node) = stack.pop() {
+            let arcs: Vec<(usize, VertexIndex)> = if reverse {
+                graph
+                    .incoming(node)
+                    .iter()
+                    .map(|&edge| (edge, graph.edge_source(edge)))
+                    .collect()
+            } else {
+                graph
+                    .edge_range(node)
+                    .map(|edge| (edge, graph.edge_target(edge)))
+                    .collect()
+            };
+            for (edge, target) in arcs {
+                let follows = workspace.predecessor(target) == Some((node, edge));
+                let switches = !follows
+                    && workspace
+                        .optimal_predecessors(target)
+                        .contains(&(node, edge))
+                    && self.prefers(graph, workspace, node, edge, target);
+                if follows || switches {
+                    workspace.set_predecessor(target, node, edge);
+                    stack.push(target);
+                }
+            }
+        }
+    }
+
+    fn prefers<V: GraphVertex, E: GraphEdge>(
+        self,
+        graph: &CsrGraph<V, E>,
+        workspace: &Workspace<E::Weight>,
+        node: VertexIndex,
+        edge: usize,
+        target: VertexIndex,
+    ) -> bool {
+        let (previous, previous_edge) = match workspace.predecessor(target) {
+            Some(predecessor) => predecessor,
+            None => return false,
+        };
+        let ordering = match self {
+            TieBreak::InsertionOrder => Ordering::Equal,
+            TieBreak::FewestHops => (workspace.hops(node) + 1).cmp(&workspace.hops(target)),
+            TieBreak::LowestVertexId => graph.vertex(node).id().cmp(graph.vertex(previous).id()),
+        };
+        ordering
+            .then_with(|| graph.edge_order(edge).cmp(&graph.edge_order(previous_edge)))
+            .is_lt()
+    }
+}
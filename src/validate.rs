@@ -0,0 +1,174 @@
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::{Edge, Graph, GraphEdge, GraphVertex, Reversed, Vertex};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphProblem {
+    DuplicateVertex { vertex: String },
+    DuplicateEdge { edge: String },
+    UnknownSource { edge: String, vertex: String },
+    UnknownDestination { edge: String, vertex: String },
+    MismatchedSource { edge: String, vertex: String },
+    MismatchedDestination { edge: String, vertex: String },
+}
+
+impl fmt::Display for GraphProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphProblem::DuplicateVertex { vertex } => {
+                write!(f, "vertex `{}` is registered more than once", vertex)
+            }
+            GraphProblem::DuplicateEdge { edge } => {
+                write!(f, "lane `{}` is registered more than once", edge)
+            }
+            GraphProblem::UnknownSource { edge, vertex } => {
+                write!(f, "lane `{}` starts at unknown vertex `{}`", edge, vertex)
+            }
+            GraphProblem::UnknownDestination { edge, vertex } => {
+                write!(f, "lane `{}` ends at unknown vertex `{}`", edge, vertex)
+            }
+            GraphProblem::MismatchedSource { edge, vertex } => write!(
+                f,
+                "lane `{}` source differs from registered vertex `{}`",
+                edge, vertex
+            ),
+            GraphProblem::MismatchedDestination { edge, vertex } => write!(
+                f,
+                "lane `{}` destination differs from registered vertex `{}`",
+                edge, vertex
+            ),
+        }
+    }
+}
+
+impl<V: GraphVertex + 'static, E: GraphEdge + 'static> Graph<V, E> {
+    pub fn try_new(vertices: Vec<V>, edges: Vec<E>) -> Result<Self, Vec<GraphProblem>> {
+        let graph = Graph::new(vertices, edges);
+        graph.validate()?;
+        Ok(graph)
+    }
+
+    pub fn validate(&self) -> Result<(), Vec<GraphProblem>> {
+        let mut problems = vec![];
+
+        let mut registered: HashMap<&str, &V> = HashMap::with_capacity(self.vertices.len());
+        for vertex in &self.vertices {
+            if registered.contains_key(vertex.id()) {
+                problems.push(GraphProblem::DuplicateVertex {
+                    vertex: vertex.id().to_string(),
+                });
+            } else {
+                registered.insert(vertex.id(), vertex);
+            }
+        }
+
+        let mut lanes = HashSet::with_capacity(self.edges.len());
+        for edge in &self.edges {
+            if !lanes.insert(edge.id()) {
+                problems.push(GraphProblem::DuplicateEdge {
+                    edge: edge.id().to_string(),
+                });
+            }
+            let embedded = embedded_vertices(edge);
+            match registered.get(edge.source()) {
+                None => problems.push(GraphProblem::UnknownSource {
+                    edge: edge.id().to_string(),
+                    vertex: edge.source().to_string(),
+                }),
+                Some(&vertex) if differs(embedded.map(|(source, _)| source), vertex) => problems
+                    .push(GraphProblem::MismatchedSource {
+                        edge: edge.id().to_string(),
+                        vertex: edge.source().to_string(),
+                    }),
+                Some(_) => {}
+            }
+            match registered.get(edge.destination()) {
+                None => problems.push(GraphProblem::UnknownDestination {
+                    edge: edge.id().to_string(),
+                    vertex: edge.destination().to_string(),
+                }),
+                Some(&vertex) if differs(embedded.map(|(_, destination)| destination), vertex) => {
+                    problems.push(GraphProblem::MismatchedDestination {
+                        edge: edge.id().to_string(),
+                        vertex: edge.destination().to_string(),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+}
+
+// Only the built-in lane type carries copies of its endpoints; custom
+// payloads name them by id alone, which the lookups above already check.
+fn embedded_vertices<E: GraphEdge + 'static>(edge: &E) -> Option<(&Vertex, &Vertex)> {
+    let edge: &dyn Any = edge;
+    if let Some(edge) = edge.downcast_ref::<Edge<E::Weight>>() {
+        return Some((&edge.source, &edge.destination));
+    }
+    edge.downcast_ref::<Reversed<Edge<E::Weight>>>()
+        .map(|Reversed(edge)| (&edge.destination, &edge.source))
+}
+
+fn differs<V: 'static>(embedded: Option<&Vertex>, registered: &V) -> bool {
+    let registered: &dyn Any = registered;
+    match (embedded, registered.downcast_ref::<Vertex>()) {
+        (Some(embedded), Some(registered)) => embedded != registered,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Edge, Vertex};
+
+    #[test]
+    fn reports_problems() {
+        let a = Vertex::new("A".into(), "A".into());
+        let b = Vertex::new("B".into(), "B".into());
+        let x = Vertex::new("X".into(), "X".into());
+        let renamed = Vertex::new("B".into(), "Bremen".into());
+        let moved = a.clone().with_coordinates(53.5, 10.0);
+        let edges = vec![
+            Edge::new("AB".into(), a.clone(), b.clone(), 1),
+            Edge::new("AB".into(), a.clone(), renamed, 1),
+            Edge::new("XA".into(), x.clone(), a.clone(), 1),
+            Edge::new("AX".into(), a.clone(), x, 1),
+            Edge::new("BA".into(), b.clone(), moved, 1),
+        ];
+        let second = Vertex::new("A".into(), "Altona".into());
+        let problems = Graph::try_new(vec![a.clone(), b.clone(), second], edges).unwrap_err();
+        assert_eq!(
+            problems,
+            vec![
+                GraphProblem::DuplicateVertex { vertex: "A".into() },
+                GraphProblem::DuplicateEdge { edge: "AB".into() },
+                GraphProblem::MismatchedDestination {
+                    edge: "AB".into(),
+                    vertex: "B".into()
+                },
+                GraphProblem::UnknownSource {
+                    edge: "XA".into(),
+                    vertex: "X".into()
+                },
+                GraphProblem::UnknownDestination {
+                    edge: "AX".into(),
+                    vertex: "X".into()
+                },
+                GraphProblem::MismatchedDestination {
+                    edge: "BA".into(),
+                    vertex: "A".into()
+                },
+            ]
+        );
+    }
+}
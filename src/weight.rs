@@ -0,0 +1,132 @@
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
+
+pub trait Weight: Copy + Ord + Debug {
+    fn zero() -> Self;
+
+    fn infinity() -> Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    fn is_negative(self) -> bool {
+        self < Self::zero()
+    }
+}
+
+macro_rules! integer_weight {
+    ($($t:ty),*) => {
+        $(
+            impl Weight for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn infinity() -> Self {
+                    <$t>::MAX
+                }
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other).filter(|&sum| sum < <$t>::MAX)
+                }
+            }
+        )*
+    };
+}
+
+integer_weight!(i32, i64, u32, u64);
+
+impl Weight for Duration {
+    fn zero() -> Self {
+        Duration::ZERO
+    }
+
+    fn infinity() -> Self {
+        Duration::MAX
+    }
+
+    fn checked_add(self, other: Self) -> Option<Self> {
+        Duration::checked_add(self, other).filter(|&sum| sum < Duration::MAX)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TotalF64(pub f64);
+
+impl PartialEq for TotalF64 {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for TotalF64 {}
+
+impl PartialOrd for TotalF64 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TotalF64 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Hash for TotalF64 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
+}
+
+impl Weight for TotalF64 {
+    fn zero() -> Self {
+        TotalF64(0.0)
+    }
+
+    fn infinity() -> Self {
+        TotalF64(f64::INFINITY)
+    }
+
+    fn checked_add(self, other: Self) -> Option<Self> {
+        let sum = self.0 + other.0;
+        if sum.is_finite() {
+            Some(TotalF64(sum))
+        } else {
+            None
+        }
+    }
+
+    fn is_negative(self) -> bool {
+        self.0 < 0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_add_stops_short_of_infinity() {
+        assert_eq!(Weight::checked_add(u32::MAX - 2, 1), Some(u32::MAX - 1));
+        assert_eq!(Weight::checked_add(u32::MAX - 1, 1), None);
+        assert_eq!(Weight::checked_add(-5i64, 3), Some(-2));
+        assert_eq!(
+            Weight::checked_add(Duration::from_secs(60), Duration::from_secs(30)),
+            Some(Duration::from_secs(90))
+        );
+        assert_eq!(
+            Weight::checked_add(Duration::MAX, Duration::from_secs(1)),
+            None
+        );
+        assert_eq!(
+            TotalF64(1.5).checked_add(TotalF64(2.0)),
+            Some(TotalF64(3.5))
+        );
+        assert_eq!(TotalF64(f64::MAX).checked_add(TotalF64(f64::MAX)), None);
+        assert!(TotalF64(-0.5).is_negative());
+        assert!(!TotalF64(-0.0).is_negative());
+        assert!(!Duration::from_secs(1).is_negative());
+    }
+}
@@ -0,0 +1,270 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
+
+use crate::{VertexIndex, Weight};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct State<W> {
+    pub(crate) cost: W,
+    pub(crate) vertex: VertexIndex,
+}
+
+impl<W: Ord> Ord for State<W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .cmp(&self.cost)
+            .then_with(|| other.vertex.cmp(&self.vertex))
+    }
+}
+
+impl<W: Ord> PartialOrd for State<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Workspace<W> {
+    generation: u32,
+    stamps: Vec<u32>,
+    settled_order: Vec<VertexIndex>,
+    settled_nodes: Vec<bool>,
+    pub(crate) unsettled_nodes: BinaryHeap<State<W>>,
+    predecessors: Vec<Option<(VertexIndex, usize)>>,
+    optimal: Vec<Vec<(VertexIndex, usize)>>,
+    distance: Vec<W>,
+    hops: Vec<u32>,
+    origins: Vec<VertexIndex>,
+}
+
+impl<W: Weight> Workspace<W> {
+    pub(crate) fn new() -> Self {
+        Workspace {
+            generation: 0,
+            stamps: Vec::new(),
+            settled_order: Vec::new(),
+            settled_nodes: Vec::new(),
+            unsettled_nodes: BinaryHeap::new(),
+            predecessors: Vec::new(),
+            optimal: Vec::new(),
+            distance: Vec::new(),
+            hops: Vec::new(),
+            origins: Vec::new(),
+        }
+    }
+
+    pub(crate) fn reset(&mut self, vertex_count: usize) {
+        self.unsettled_nodes.clear();
+        self.settled_order.clear();
+        if self.stamps.len() != vertex_count {
+            self.stamps.resize(vertex_count, 0);
+            self.settled_nodes.resize(vertex_count, false);
+            self.predecessors.resize(vertex_count, None);
+            self.optimal.resize_with(vertex_count, Vec::new);
+            self.distance.resize(vertex_count, W::infinity());
+            self.hops.resize(vertex_count, 0);
+            self.origins.resize(vertex_count, VertexIndex(0));
+        }
+        self.generation = self.generation.wrapping_add(1);
+        if self.generation == 0 {
+            self.stamps.iter_mut().for_each(|stamp| *stamp = 0);
+            self.generation = 1;
+        }
+    }
+
+    fn is_touched(&self, vertex: VertexIndex) -> bool {
+        self.stamps.get(vertex.index()) == Some(&self.generation)
+    }
+
+    fn touch(&mut self, vertex: VertexIndex) {
+        let index = vertex.index();
+        if self.stamps[index] != self.generation {
+            self.stamps[index] = self.generation;
+            self.settled_nodes[index] = false;
+            self.predecessors[index] = None;
+            self.optimal[index].clear();
+            self.distance[index] = W::infinity();
+            self.hops[index] = 0;
+            self.origins[index] = vertex;
+        }
+    }
+
+    pub(crate) fn is_settled(&self, vertex: VertexIndex) -> bool {
+        self.is_touched(vertex) && self.settled_nodes[vertex.index()]
+    }
+
+    pub(crate) fn settle(&mut self, vertex: VertexIndex) {
+        self.touch(vertex);
+        self.settled_nodes[vertex.index()] = true;
+        self.settled_order.push(vertex);
+    }
+
+    pub(crate) fn pop_unsettled(&mut self) -> Option<State<W>> {
+        self.min_cost()?;
+        self.unsettled_nodes.pop()
+    }
+
+    pub(crate) fn min_cost(&mut self) -> Option<W> {
+        while let Some(&State { cost, vertex }) = self.unsettled_nodes.peek() {
+            if self.is_settled(vertex) {
+                self.unsettled_nodes.pop();
+            } else {
+                return Some(cost);
+            }
+        }
+        None
+    }
+
+    pub(crate) fn settled(&self) -> &[VertexIndex] {
+        &self.settled_order
+    }
+
+    pub(crate) fn distance(&self, vertex: VertexIndex) -> W {
+        if self.is_touched(vertex) {
+            self.distance[vertex.index()]
+        } else {
+            W::infinity()
+        }
+    }
+
+    pub(crate) fn predecessor(&self, vertex: VertexIndex) -> Option<(VertexIndex, usize)> {
+        if self.is_touched(vertex) {
+            self.predecessors[vertex.index()]
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn optimal_predecessors(&self, vertex: VertexIndex) -> &[(VertexIndex, usize)] {
+        if self.is_touched(vertex) {
+            &self.optimal[vertex.index()]
+        } else {
+            &[]
+        }
+    }
+
+    pub(crate) fn is_tied_ancestor(&self, ancestor: VertexIndex, vertex: VertexIndex) -> bool {
+        let cost = self.distance(vertex);
+        let mut stack = vec![vertex];
+        let mut seen = HashSet::new();
+        while let Some(step) = stack.pop() {
+            if step == ancestor {
+                return true;
+            }
+            if seen.insert(step) {
+                stack.extend(
+                    self.optimal_predecessors(step)
+                        .iter()
+                        .map(|&(previous, _)| previous)
+                        .filter(|&previous| self.distance(previous) == cost),
+                );
+            }
+        }
+        false
+    }
+
+    pub(crate) fn hops(&self, vertex: VertexIndex) -> u32 {
+        if self.is_touched(vertex) {
+            self.hops[vertex.index()]
+        } else {
+            0
+        }
+    }
+
+    pub(crate) fn origin(&self, vertex: VertexIndex) -> Option<VertexIndex> {
+        if self.is_touched(vertex) {
+            Some(self.origins[vertex.index()])
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn start(&mut self, vertex: VertexIndex, cost: W) {
+        self.start_with_priority(vertex, cost, cost);
+    }
+
+    pub(crate) fn start_with_priority(&mut self, vertex: VertexIndex, cost: W, priority: W) {
+        self.touch(vertex);
+        self.distance[vertex.index()] = cost;
+        self.unsettled_nodes.push(State {
+            cost: priority,
+            vertex,
+        });
+    }
+
+    pub(crate) fn improve(&mut self, vertex: VertexIndex, cost: W, node: VertexIndex, edge: usize) {
+        self.improve_with_priority(vertex, cost, cost, node, edge);
+    }
+
+    pub(crate) fn improve_with_priority(
+        &mut self,
+        vertex: VertexIndex,
+        cost: W,
+        priority: W,
+        node: VertexIndex,
+        edge: usize,
+    ) {
+        self.touch(vertex);
+        self.distance[vertex.index()] = cost;
+        self.optimal[vertex.index()].clear();
+        self.add_optimal_predecessor(vertex, node, edge);
+        self.set_predecessor(vertex, node, edge);
+        self.unsettled_nodes.push(State {
+            cost: priority,
+            vertex,
+        });
+    }
+
+    pub(crate) fn add_optimal_predecessor(
+        &mut self,
+        vertex: VertexIndex,
+        node: VertexIndex,
+        edge: usize,
+    ) {
+        self.touch(vertex);
+        self.optimal[vertex.index()].push((node, edge));
+    }
+
+    pub(crate) fn set_predecessor(&mut self, vertex: VertexIndex, node: VertexIndex, edge: usize) {
+        self.touch(vertex);
+        self.predecessors[vertex.index()] = Some((node, edge));
+        self.hops[vertex.index()] = self.hops(node) + 1;
+        self.origins[vertex.index()] = self.origins[node.index()];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reset_forgets_previous_generation() {
+        let mut workspace = Workspace::<u32>::new();
+        workspace.reset(3);
+        workspace.start(VertexIndex(0), 0);
+        workspace.improve(VertexIndex(1), 5, VertexIndex(0), 0);
+        workspace.settle(VertexIndex(1));
+        assert_eq!(workspace.settled(), &[VertexIndex(1)]);
+        assert_eq!(workspace.distance(VertexIndex(1)), 5);
+        assert_eq!(workspace.hops(VertexIndex(1)), 1);
+        assert_eq!(
+            workspace.optimal_predecessors(VertexIndex(1)),
+            &[(VertexIndex(0), 0)]
+        );
+
+        workspace.reset(3);
+        assert!(workspace.unsettled_nodes.is_empty());
+        assert!(workspace.settled().is_empty());
+        assert!(!workspace.is_settled(VertexIndex(1)));
+        assert_eq!(workspace.distance(VertexIndex(1)), u32::MAX);
+        assert_eq!(workspace.predecessor(VertexIndex(1)), None);
+        assert!(workspace.optimal_predecessors(VertexIndex(1)).is_empty());
+
+        workspace.generation = u32::MAX;
+        workspace.start(VertexIndex(2), 7);
+        workspace.reset(3);
+        assert_eq!(workspace.generation, 1);
+        assert_eq!(workspace.distance(VertexIndex(2)), u32::MAX);
+    }
+}
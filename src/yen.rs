@@ -0,0 +1,183 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+use crate::workspace::{State, Workspace};
+use crate::{DijkstraError, Djikstra, GraphEdge, GraphVertex, Path, VertexIndex, Weight};
+
+type Route<W> = (W, Vec<usize>);
+
+impl<V: GraphVertex + Clone, E: GraphEdge + Clone> Djikstra<V, E> {
+    pub fn k_shortest_paths<S, T>(
+        &mut self,
+        source: &S,
+        target: &T,
+        k: usize,
+    ) -> Result<Vec<Path<V, E>>, DijkstraError>
+    where
+        S: GraphVertex + ?Sized,
+        T: GraphVertex + ?Sized,
+    {
+        if k == 0 {
+            return Ok(vec![]);
+        }
+        let first = self.run_to(source, target)?;
+        let from = self.graph.vertex_index(first.source().id()).unwrap();
+        let to = self.graph.vertex_index(first.target().id()).unwrap();
+        let mut accepted = vec![(first.cost, arcs_to(&self.workspace, to))];
+        let mut candidates: BinaryHeap<Reverse<Route<E::Weight>>> = BinaryHeap::new();
+        let mut seen = HashSet::new();
+        seen.insert(accepted[0].1.clone());
+        let mut banned_vertices = vec![false; self.graph.vertex_count()];
+        // Spur searches run in their own workspace so the unrestricted search
+        // above stays readable through `get_route` and friends.
+        let mut scratch = Workspace::new();
+
+        while accepted.len() < k {
+            let previous = accepted[accepted.len() - 1].1.clone();
+            let mut spur = from;
+            let mut root_cost = E::Weight::zero();
+            for i in 0..previous.len() {
+                let root = &previous[..i];
+                let banned_arcs: Vec<usize> = accepted
+                    .iter()
+                    .filter(|(_, arcs)| arcs.len() > i && arcs.starts_with(root))
+                    .map(|(_, arcs)| arcs[i])
+                    .collect();
+                banned_vertices[spur.index()] = true;
+
+                if let Some((cost, spur_arcs)) =
+                    self.spur_path(&mut scratch, spur, to, &banned_vertices, &banned_arcs)?
+                {
+                    let total = root_cost.checked_add(cost).ok_or(DijkstraError::Overflow)?;
+                    let mut arcs = root.to_vec();
+                    arcs.extend(spur_arcs);
+                    if seen.insert(arcs.clone()) {
+                        candidates.push(Reverse((total, arcs)));
+                    }
+                }
+
+                root_cost = root_cost
+                    .checked_add(self.graph.edge_weight(previous[i]))
+                    .ok_or(DijkstraError::Overflow)?;
+                spur = self.graph.edge_target(previous[i]);
+            }
+            banned_vertices
+                .iter_mut()
+                .for_each(|banned| *banned = false);
+
+            match candidates.pop() {
+                Some(Reverse(candidate)) => accepted.push(candidate),
+                None => break,
+            }
+        }
+
+        Ok(accepted
+            .iter()
+            .map(|(cost, arcs)| Path::from_arcs(&self.graph, from, arcs, *cost))
+            .collect())
+    }
+
+    fn spur_path(
+        &self,
+        scratch: &mut Workspace<E::Weight>,
+        spur: VertexIndex,
+        target: VertexIndex,
+        banned_vertices: &[bool],
+        banned_arcs: &[usize],
+    ) -> Result<Option<Route<E::Weight>>, DijkstraError> {
+        scratch.reset(self.graph.vertex_count());
+        scratch.start(spur, E::Weight::zero());
+
+        while let Some(State { vertex: node, .. }) = scratch.pop_unsettled() {
+            scratch.settle(node);
+            if node == target {
+                return Ok(Some((scratch.distance(target), arcs_to(scratch, target))));
+            }
+            for edge in self.graph.edge_range(node) {
+                let next = self.graph.edge_target(edge);
+                if banned_vertices[next.index()]
+                    || banned_arcs.contains(&edge)
+                    || scratch.is_settled(next)
+                {
+                    continue;
+                }
+                let cost = scratch
+                    .distance(node)
+                    .checked_add(self.graph.edge_weight(edge))
+                    .ok_or(DijkstraError::Overflow)?;
+                if cost < scratch.distance(next) {
+                    scratch.improve(next, cost, node, edge);
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+fn arcs_to<W: Weight>(workspace: &Workspace<W>, target: VertexIndex) -> Vec<usize> {
+    let mut arcs = vec![];
+    let mut step = target;
+    while let Some((previous, edge)) = workspace.predecessor(step) {
+        arcs.push(edge);
+        step = previous;
+    }
+    arcs.reverse();
+    arcs
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{add_lane, vertices};
+    use crate::{DijkstraError, Direction, Djikstra, Graph};
+
+    fn network() -> Graph {
+        let nodes = vertices(&["C", "D", "E", "F", "G", "H", "X"]);
+        let mut edges = vec![];
+        add_lane(&nodes, &mut edges, "CD".into(), 0, 1, 3);
+        add_lane(&nodes, &mut edges, "CE".into(), 0, 2, 2);
+        add_lane(&nodes, &mut edges, "DF".into(), 1, 3, 4);
+        add_lane(&nodes, &mut edges, "ED".into(), 2, 1, 1);
+        add_lane(&nodes, &mut edges, "EF".into(), 2, 3, 2);
+        add_lane(&nodes, &mut edges, "EG".into(), 2, 4, 3);
+        add_lane(&nodes, &mut edges, "FG".into(), 3, 4, 2);
+        add_lane(&nodes, &mut edges, "FH".into(), 3, 5, 1);
+        add_lane(&nodes, &mut edges, "GH".into(), 4, 5, 2);
+        add_lane(&nodes, &mut edges, "HC".into(), 5, 0, 1);
+        edges[9].direction = Direction::Symmetric;
+        Graph::new(nodes, edges)
+    }
+
+    #[test]
+    fn ranked_alternatives() {
+        let mut djikstra = Djikstra::new(network());
+        let paths = djikstra.k_shortest_paths("C", "H", 5).unwrap();
+
+        assert_eq!(djikstra.get_route("H").unwrap(), paths[0]);
+        assert_eq!(djikstra.distance_to("H"), Ok(1));
+        let costs: Vec<_> = paths.iter().map(|path| path.cost).collect();
+        assert_eq!(costs, vec![1, 5, 7, 8, 8]);
+        assert_eq!(paths[0].edges, vec!["HC"]);
+        assert_eq!(paths[1].vertex_ids(), vec!["C", "E", "F", "H"]);
+        assert_eq!(paths[2].vertex_ids(), vec!["C", "E", "G", "H"]);
+        let mut tied: Vec<_> = paths[3..].iter().map(|path| path.vertex_ids()).collect();
+        tied.sort();
+        assert_eq!(
+            tied,
+            vec![vec!["C", "D", "F", "H"], vec!["C", "E", "D", "F", "H"]]
+        );
+        for path in &paths {
+            assert_eq!(path.weights.iter().sum::<i32>(), path.cost);
+            let mut visited = path.vertex_ids();
+            visited.sort();
+            visited.dedup();
+            assert_eq!(visited.len(), path.vertices.len());
+        }
+
+        assert_eq!(djikstra.k_shortest_paths("D", "C", 10).unwrap().len(), 2);
+        assert!(djikstra.k_shortest_paths("C", "H", 0).unwrap().is_empty());
+        assert_eq!(
+            djikstra.k_shortest_paths("C", "X", 3),
+            Err(DijkstraError::Unreachable("X".into()))
+        );
+    }
+}